@@ -40,4 +40,196 @@ pub enum PresaleError {
     #[msg("Numerical Overflow Error")]
     NumericalOverflowError,
 
+    #[msg("The presale does not use per-purchase vesting lots")]
+    LotVestingDisabled,
+
+    #[msg("The vesting lots account is missing or does not belong to the vesting account")]
+    MissingVestingLots,
+
+    #[msg("Too many vesting lots are still maturing, another purchase has to wait until the oldest one matures")]
+    TooManyVestingLots,
+
+    #[msg("The vesting schedule parameters are invalid")]
+    InvalidVestingSchedule,
+
+    #[msg("The presale does not defer its vesting start")]
+    VestingNotDeferred,
+
+    #[msg("The vesting start has already been set")]
+    VestingStartAlreadySet,
+
+    #[msg("The vesting start must be between the presale end and the committed deadline")]
+    InvalidVestingStart,
+
+    #[msg("The milestones are invalid")]
+    InvalidMilestones,
+
+    #[msg("The milestone does not exist")]
+    MilestoneNotFound,
+
+    #[msg("The milestone has already been reached")]
+    MilestoneAlreadyReached,
+
+    #[msg("The presale does not allow leaving vesting early")]
+    EarlyExitDisabled,
+
+    #[msg("The vesting period has already finished")]
+    VestingPeriodFinished,
+
+    #[msg("The lockup options are invalid")]
+    InvalidLockupOptions,
+
+    #[msg("The presale does not offer this lockup option")]
+    LockupOptionNotFound,
+
+    #[msg("Every purchase must use the lockup chosen on the first one")]
+    LockupOptionMismatch,
+
+    #[msg("The presale authority must approve vesting position transfers")]
+    TransferNotApproved,
+
+    #[msg("The presale cannot require approval for transfers of tokenized positions")]
+    InvalidTransferPolicy,
+
+    #[msg("The vesting receipt is missing or not held by the signer")]
+    MissingVestingReceipt,
+
+    #[msg("The signer does not own this vesting position")]
+    NotPositionOwner,
+
+    #[msg("Tokenized positions change hands by transferring their receipt")]
+    PositionIsTokenized,
+
+    #[msg("The vesting account still holds fractions that have not been claimed")]
+    VestingAccountNotEmpty,
+
+    #[msg("The amount is more than can currently be unlocked")]
+    AmountExceedsUnlockable,
+
+    #[msg("Delegates can only unlock fractions into the owner's own accounts")]
+    DelegateMustPayOwner,
+
+    #[msg("There are no unvested revocable fractions left to revoke")]
+    NothingToRevoke,
+
+    #[msg("Positions holding a revocable grant cannot leave vesting early")]
+    GrantIsRevocable,
+
+    #[msg("The airdrop recipient accounts do not match the allocations")]
+    InvalidAirdropAccounts,
+
+    #[msg("The vesting account does not belong to this vesting position")]
+    InvalidVestingAccount,
+
+    #[msg("The presale pools vesting fractions in a shared escrow")]
+    VestingIsPooled,
+
+    #[msg("The signer is not the pending authority of the presale")]
+    NotPendingAuthority,
+
+    #[msg("The multisig owners must be unique and there can be at most ten of them")]
+    InvalidMultisigOwners,
+
+    #[msg("The multisig threshold must be between one and the number of owners")]
+    InvalidMultisigThreshold,
+
+    #[msg("The signer is not an owner of the multisig")]
+    NotMultisigOwner,
+
+    #[msg("The transaction has too many accounts or too much data")]
+    TransactionTooLarge,
+
+    #[msg("The transaction has already been executed")]
+    TransactionAlreadyExecuted,
+
+    #[msg("The transaction does not have enough approvals")]
+    NotEnoughApprovals,
+
+    #[msg("The multisig owners changed after the transaction was proposed")]
+    MultisigOwnersChanged,
+
+    #[msg("These presale parameters cannot be changed once the presale has started")]
+    InvalidPresaleParams,
+
+    #[msg("The price cannot move this far from the price the presale started at")]
+    PriceChangeTooLarge,
+
+    #[msg("This change has to be queued while the presale is timelocked")]
+    TimelockActive,
+
+    #[msg("The timelock delay cannot be longer than thirty days")]
+    InvalidTimelockDelay,
+
+    #[msg("The queued action cannot be executed before its timelock expires")]
+    TimelockNotExpired,
+
+    #[msg("The accounts needed by the queued action are missing or invalid")]
+    InvalidActionAccounts,
+
+    #[msg("Purchases are paused")]
+    PresalePaused,
+
+    #[msg("Beneficiary shares must add up to 10000 basis points across distinct token accounts")]
+    InvalidBeneficiaries,
+
+    #[msg("The beneficiary token accounts do not match the presale's beneficiaries")]
+    InvalidBeneficiaryAccounts,
+
+    #[msg("The presale has no beneficiaries to distribute funds to")]
+    NoBeneficiaries,
+
+    #[msg("Raised funds are split among the beneficiaries by `distribute_funds`")]
+    FundsAreSplit,
+
+    #[msg("Only the program's upgrade authority can create the program config")]
+    NotUpgradeAuthority,
+
+    #[msg("The platform fee cannot be more than 1000 basis points")]
+    InvalidFee,
+
+    #[msg("The platform fee account is missing or does not match")]
+    InvalidFeeAccount,
+
+    #[msg("The program config account does not match")]
+    InvalidProgramConfig,
+
+    #[msg("The presale index does not belong to this fraction mint")]
+    InvalidPresaleIndex,
+
+    #[msg("This page of the presale index is full")]
+    PresaleIndexFull,
+
+    #[msg("The presale has not finished yet")]
+    PresaleNotFinished,
+
+    #[msg("The presale has already been finalized")]
+    PresaleAlreadyFinalized,
+
+    #[msg("Unsold fractions can only be returned to the fraction account fixed when the presale was created")]
+    InvalidUnsoldDestination,
+
+    #[msg("The presale must be finalized before it can be closed")]
+    PresaleNotFinalized,
+
+    #[msg("Vesting positions are still open")]
+    PositionsStillOpen,
+
+    #[msg("The raised funds must be collected before the presale can be closed")]
+    FundsNotCollected,
+
+    #[msg("Presales with vesting receipts can only airdrop into positions that already have one")]
+    AirdropNeedsVestingReceipt,
+
+    #[msg("This position was fully claimed and its receipt burned")]
+    VestingReceiptBurned,
+
+    #[msg("Grants and airdrops must be approved through the timelock queue first")]
+    GrantNotApproved,
+
+    #[msg("A new page of the presale index can only be opened once the previous one is full")]
+    PresaleIndexPageNotFull,
+
+    #[msg("Fractions are still vesting or owed to buyers")]
+    FractionsStillVesting,
+
 }
\ No newline at end of file
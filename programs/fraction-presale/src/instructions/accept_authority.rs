@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+
+    #[account(
+        mut,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub pending_authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<AcceptAuthority>) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    if presale_account.pending_authority == Pubkey::default()
+        || presale_account.pending_authority != ctx.accounts.pending_authority.key()
+    {
+        return Err(PresaleError::NotPendingAuthority.into());
+    }
+
+    // Roles left with the old authority move along with it
+    let pending_authority = presale_account.pending_authority;
+    presale_account.transfer_authority(pending_authority);
+    Ok(())
+}
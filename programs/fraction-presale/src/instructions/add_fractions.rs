@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
 
 use crate::state::*;
 use crate::errors::*;
@@ -9,12 +9,12 @@ use crate::errors::*;
 pub struct AddFractions<'info> {
 
     // The constraints on this account ensure that we're dealing with the right treasuries
-    // and that the instruction is being called by the correct authority
+    // and that the instruction is being called by the correct operator
     #[account(
         mut,
         has_one = fraction_treasury,
         has_one = payment_treasury,
-        has_one = authority,
+        has_one = operator,
         owner = crate::id()
     )]
     pub presale_account: Account<'info, PresaleInfo>,
@@ -29,7 +29,7 @@ pub struct AddFractions<'info> {
     #[account(
         mut,
         constraint = from_account.mint == fraction_treasury.mint,
-        constraint = from_account.owner == authority.key()
+        constraint = from_account.owner == operator.key()
     )]
     pub from_account: Account<'info, TokenAccount>,
 
@@ -39,14 +39,14 @@ pub struct AddFractions<'info> {
     )]
     pub presale_pda: AccountInfo<'info>,
 
-    pub authority: Signer<'info>,
+    pub operator: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 
 }
 
 
-pub fn handler(ctx: Context<AddFractions>, amount: u64) -> ProgramResult {
+pub(crate) fn handler(ctx: Context<AddFractions>, amount: u64) -> ProgramResult {
     if amount == 0 { return Err(PresaleError::AmountIsZero.into()); }
 
     let from_account = &ctx.accounts.from_account;
@@ -62,7 +62,7 @@ pub fn handler(ctx: Context<AddFractions>, amount: u64) -> ProgramResult {
             Transfer {
                 from: ctx.accounts.from_account.to_account_info(),
                 to: ctx.accounts.fraction_treasury.to_account_info(),
-                authority: ctx.accounts.authority.to_account_info()
+                authority: ctx.accounts.operator.to_account_info()
             }
         ), 
         amount
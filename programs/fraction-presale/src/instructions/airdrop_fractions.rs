@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::{self, InitializeAccount, Transfer, Token, Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+// Every recipient is passed as its wallet, its `VestingInfo` PDA and its vesting token account
+const ACCOUNTS_PER_RECIPIENT: usize = 3;
+
+#[derive(Accounts)]
+#[instruction(presale_pda_bump: u8)]
+pub struct AirdropFractions<'info> {
+
+    #[account(
+        mut,
+        has_one = fraction_treasury,
+        has_one = fraction_mint,
+        has_one = operator,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    #[account(mut)]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = presale_pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    // Pays for the vesting positions of recipients who don't have one yet
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, AirdropFractions<'info>>, presale_pda_bump: u8, allocations: Vec<AirdropAllocation>) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Lots would need every recipient's lots account as well
+    if presale_account.lot_duration > 0 {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    if allocations.is_empty() || ctx.remaining_accounts.len() != allocations.len() * ACCOUNTS_PER_RECIPIENT {
+        return Err(PresaleError::InvalidAirdropAccounts.into());
+    }
+
+    let mut total_amount: u64 = 0;
+    for allocation in allocations.iter() {
+        if allocation.amount == 0 { return Err(PresaleError::AmountIsZero.into()); }
+        total_amount = match total_amount.checked_add(allocation.amount) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+    }
+    if presale_account.available_fractions(ctx.accounts.fraction_treasury.amount) < total_amount {
+        return Err(PresaleError::NotEnoughTokensInFractionTreasury.into());
+    }
+    presale_account.spend_grant_allowance(total_amount)?;
+
+    let presale_key = presale_account.key();
+    for (allocation, accounts) in allocations.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_RECIPIENT)) {
+        let recipient = &accounts[0];
+        let vesting_pda = &accounts[1];
+        let vesting_account = &accounts[2];
+
+        let vesting_seeds: &[&[u8]] = &[b"vesting".as_ref(), recipient.key.as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[allocation.vesting_pda_bump]];
+        match Pubkey::create_program_address(vesting_seeds, ctx.program_id) {
+            Ok(key) if key == *vesting_pda.key && vesting_pda.is_writable => {},
+            _ => return Err(PresaleError::InvalidAirdropAccounts.into()),
+        }
+
+        // Pooled positions all share the escrow
+        if presale_account.is_vesting_pooled() && *vesting_account.key != presale_account.vesting_escrow {
+            return Err(PresaleError::InvalidAirdropAccounts.into());
+        }
+
+        // Receipts are minted to a token account the recipient signs for, so positions can't be opened for them here.
+        // Otherwise recipients without a position get one, with its token account at a PDA so nobody else needs to sign
+        let new_position = vesting_pda.data_is_empty();
+        if new_position && presale_account.vesting_receipts {
+            return Err(PresaleError::AirdropNeedsVestingReceipt.into());
+        }
+        if new_position && !presale_account.is_vesting_pooled() {
+            let vesting_account_seeds: &[&[u8]] = &[b"vesting_account".as_ref(), vesting_pda.key.as_ref(), ctx.program_id.as_ref(), &[allocation.vesting_account_bump]];
+            match Pubkey::create_program_address(vesting_account_seeds, ctx.program_id) {
+                Ok(key) if key == *vesting_account.key && vesting_account.is_writable => {},
+                _ => return Err(PresaleError::InvalidAirdropAccounts.into()),
+            }
+
+            create_account(
+                &ctx.accounts.operator.to_account_info(),
+                vesting_account,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.rent,
+                spl_token::state::Account::LEN,
+                &token::ID,
+                &[vesting_account_seeds]
+            )?;
+            token::initialize_account(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    InitializeAccount {
+                        account: vesting_account.clone(),
+                        mint: ctx.accounts.fraction_mint.to_account_info(),
+                        authority: vesting_pda.clone(),
+                        rent: ctx.accounts.rent.to_account_info()
+                    }
+                )
+            )?;
+        }
+
+        if new_position {
+            create_account(
+                &ctx.accounts.operator.to_account_info(),
+                vesting_pda,
+                &ctx.accounts.system_program.to_account_info(),
+                &ctx.accounts.rent,
+                8 + VESTING_INFO_SIZE,
+                ctx.program_id,
+                &[vesting_seeds]
+            )?;
+            let mut new_vesting_info = VestingInfo::default();
+            new_vesting_info.open(*recipient.key, *vesting_account.key);
+            new_vesting_info.try_serialize(&mut &mut vesting_pda.try_borrow_mut_data()?[..])?;
+            presale_account.position_opened()?;
+        }
+
+        let mut vesting_info: Account<'info, VestingInfo> = Account::try_from(vesting_pda)?;
+        if vesting_info.vesting_account != *vesting_account.key {
+            return Err(PresaleError::InvalidAirdropAccounts.into());
+        }
+        if presale_account.vesting_receipts && !vesting_info.is_tokenized() {
+            return Err(PresaleError::AirdropNeedsVestingReceipt.into());
+        }
+        if vesting_info.is_receipt_burned() {
+            return Err(PresaleError::VestingReceiptBurned.into());
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                Transfer {
+                    from: ctx.accounts.fraction_treasury.to_account_info(),
+                    to: vesting_account.clone(),
+                    authority: ctx.accounts.presale_pda.to_account_info()
+                },
+                &[&[b"presale".as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[presale_pda_bump]]]
+            ), 
+            allocation.amount
+        )?;
+
+        presale_account.vest(&mut vesting_info, allocation.amount)?;
+        vesting_info.exit(ctx.program_id)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ApproveTransaction<'info> {
+
+    #[account(owner = crate::id())]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        owner = crate::id()
+    )]
+    pub transaction: Box<Account<'info, MultisigTransaction>>,
+
+    pub owner: Signer<'info>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<ApproveTransaction>) -> ProgramResult {
+    let multisig = &ctx.accounts.multisig;
+    let transaction = &mut ctx.accounts.transaction;
+
+    if transaction.owner_set_seqno != multisig.owner_set_seqno {
+        return Err(PresaleError::MultisigOwnersChanged.into());
+    }
+    if transaction.did_execute {
+        return Err(PresaleError::TransactionAlreadyExecuted.into());
+    }
+
+    let owner_index = multisig.owner_index(ctx.accounts.owner.key)?;
+    transaction.signers[owner_index] = true;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CancelAction<'info> {
+
+    #[account(
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    #[account(
+        mut,
+        has_one = presale_account,
+        has_one = payer,
+        close = payer,
+        owner = crate::id()
+    )]
+    pub queued_action: Box<Account<'info, QueuedAction>>,
+
+    // The presale authority, the treasurer for beneficiary changes, or the operator for treasury fractions
+    #[account(constraint = authority.key() == queued_action.action.role(&presale_account))]
+    pub authority: Signer<'info>,
+
+    // Gets back the rent it paid when the action was queued
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+
+}
+
+
+// Closing the account is all there is to it
+pub(crate) fn handler(_ctx: Context<CancelAction>) -> ProgramResult {
+    Ok(())
+}
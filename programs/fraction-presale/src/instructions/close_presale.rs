@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, CloseAccount, Token, Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::finalize_presale::release_unsold_fractions;
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8)]
+pub struct ClosePresale<'info> {
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = fraction_treasury,
+        has_one = payment_treasury,
+        has_one = access_treasury,
+        has_one = fraction_mint,
+        has_one = access_mint,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    // The page of the mint's index the presale is listed in
+    #[account(
+        mut,
+        constraint = presale_index.fraction_mint == presale_account.fraction_mint,
+        owner = crate::id()
+    )]
+    pub presale_index: Box<Account<'info, PresaleIndex>>,
+
+    #[account(mut)]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payment_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub access_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub access_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    // Receives the rent of every account being closed
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+// `remaining_accounts` holds the unsold destination when unsold fractions are returned, followed by the
+// vesting escrow when the presale is pooled
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClosePresale<'info>>, pda_bump: u8) -> ProgramResult {
+    let presale_account = &ctx.accounts.presale_account;
+
+    if !presale_account.finalized {
+        return Err(PresaleError::PresaleNotFinalized.into());
+    }
+    // Empty positions are left for their owners to close, only fractions still owed to them hold the presale open
+    if presale_account.fractions_vesting > 0 || presale_account.penalty_pool > 0 {
+        return Err(PresaleError::FractionsStillVesting.into());
+    }
+    if ctx.accounts.payment_treasury.amount > 0 {
+        return Err(PresaleError::FundsNotCollected.into());
+    }
+
+    // With nothing vesting no fraction in the treasury is owed to a buyer any more,
+    // so what early exits returned since finalizing goes the way of the unsold fractions
+    release_unsold_fractions(
+        presale_account,
+        &ctx.accounts.fraction_treasury,
+        ctx.accounts.fraction_mint.to_account_info(),
+        ctx.remaining_accounts,
+        ctx.accounts.presale_pda.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        pda_bump,
+        ctx.accounts.fraction_treasury.amount
+    )?;
+
+    let presale_key = presale_account.key();
+    let presale_seeds: &[&[u8]] = &[b"presale".as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[pda_bump]];
+
+    // Access tokens spent on purchases have no further use
+    if ctx.accounts.access_treasury.amount > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                Burn {
+                    mint: ctx.accounts.access_mint.to_account_info(),
+                    to: ctx.accounts.access_treasury.to_account_info(),
+                    authority: ctx.accounts.presale_pda.to_account_info()
+                },
+                &[presale_seeds]
+            ), 
+            ctx.accounts.access_treasury.amount
+        )?;
+    }
+
+    let mut token_accounts = vec![
+        ctx.accounts.fraction_treasury.to_account_info(),
+        ctx.accounts.payment_treasury.to_account_info(),
+        ctx.accounts.access_treasury.to_account_info(),
+    ];
+    if presale_account.is_vesting_pooled() {
+        let escrow_index = if presale_account.unsold_destination == Pubkey::default() { 0 } else { 1 };
+        let escrow_info = match ctx.remaining_accounts.get(escrow_index) {
+            Some(info) if info.key() == presale_account.vesting_escrow && info.is_writable => info,
+            _ => return Err(PresaleError::InvalidVestingAccount.into()),
+        };
+
+        // Nothing in the escrow belongs to a position any more, including fractions sent to it directly
+        let vesting_escrow: Account<'info, TokenAccount> = Account::try_from(escrow_info)?;
+        release_unsold_fractions(
+            presale_account,
+            &vesting_escrow,
+            ctx.accounts.fraction_mint.to_account_info(),
+            ctx.remaining_accounts,
+            ctx.accounts.presale_pda.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            pda_bump,
+            vesting_escrow.amount
+        )?;
+        token_accounts.push(escrow_info.clone());
+    }
+
+    for token_account in token_accounts {
+        token::close_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: token_account,
+                    destination: ctx.accounts.authority.to_account_info(),
+                    authority: ctx.accounts.presale_pda.to_account_info()
+                },
+                &[presale_seeds]
+            )
+        )?;
+    }
+
+    // Clients discovering presales through the index no longer find this one
+    ctx.accounts.presale_index.presales.retain(|presale| *presale != presale_key);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountsClose;
+use anchor_spl::token::{self, CloseAccount, Transfer, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(vesting_pda_bump: u8)]
+pub struct CloseVestingAccount<'info> {
+
+    // Checked in the handler, since positions can still be closed once `close_presale` has closed the presale
+    #[account(mut)]
+    pub presale_account: AccountInfo<'info>,
+
+    // The position's own token account, or the pooled escrow, which is gone once the presale is closed
+    #[account(mut)]
+    pub vesting_account: AccountInfo<'info>,
+
+    // Fractions sent to the vesting account on top of the position are swept to its owner
+    #[account(
+        mut,
+        constraint = to_account.owner == user_vesting_pda.owner
+    )]
+    pub to_account: Box<Account<'info, TokenAccount>>,
+
+    // Rent goes back to the buyer who opened the position, whoever owns it by now
+    #[account(
+        mut,
+        close = signer,
+        seeds = [b"vesting".as_ref(), signer.key().as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = vesting_pda_bump,
+        has_one = vesting_account,
+        has_one = signer,
+        owner = crate::id()
+    )]
+    pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseVestingAccount<'info>>, vesting_pda_bump: u8) -> ProgramResult {
+    let user_vesting_pda = &ctx.accounts.user_vesting_pda;
+
+    // Nothing may be left to vest, to claim, or to be paid out of redistributed penalties
+    if user_vesting_pda.locked_amount() > 0 || user_vesting_pda.penalty_rewards > 0 {
+        return Err(PresaleError::VestingAccountNotEmpty.into());
+    }
+
+    // A closed presale has nothing left to update, and its lots and pooled escrow are told apart by what was passed
+    let presale_info = ctx.accounts.presale_account.to_account_info();
+    let (has_lots, pooled) = if presale_info.owner == ctx.program_id {
+        let mut presale_account: Account<'info, PresaleInfo> = Account::try_from(&presale_info)?;
+        presale_account.open_positions = presale_account.open_positions.saturating_sub(1);
+        presale_account.exit(ctx.program_id)?;
+        (presale_account.lot_duration > 0, presale_account.is_vesting_pooled())
+    } else {
+        (!ctx.remaining_accounts.is_empty(), ctx.accounts.vesting_account.data_is_empty())
+    };
+
+    // Lots are closed along with the position they belong to
+    if has_lots {
+        let vesting_lots = VestingLots::load(ctx.remaining_accounts, &user_vesting_pda.key())?;
+        vesting_lots.close(ctx.accounts.signer.to_account_info())?;
+    }
+
+    // The pooled escrow holds everyone else's fractions too, and stays open for the remaining positions
+    if pooled {
+        return Ok(());
+    }
+
+    let vesting_account: Account<'info, TokenAccount> = Account::try_from(&ctx.accounts.vesting_account)?;
+    if vesting_account.owner != user_vesting_pda.key() {
+        return Err(PresaleError::InvalidVestingAccount.into());
+    }
+
+    let vesting_seeds: &[&[u8]] = &[b"vesting".as_ref(), ctx.accounts.signer.key.as_ref(), presale_info.key.as_ref(), ctx.program_id.as_ref(), &[vesting_pda_bump]];
+
+    if vesting_account.amount > 0 {
+        if ctx.accounts.to_account.mint != vesting_account.mint {
+            return Err(PresaleError::InvalidVestingAccount.into());
+        }
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vesting_account.to_account_info(),
+                    to: ctx.accounts.to_account.to_account_info(),
+                    authority: user_vesting_pda.to_account_info()
+                },
+                &[vesting_seeds]
+            ),
+            vesting_account.amount
+        )?;
+    }
+
+    token::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vesting_account.to_account_info(),
+                destination: ctx.accounts.signer.to_account_info(),
+                authority: user_vesting_pda.to_account_info()
+            },
+            &[vesting_seeds]
+        )
+    )?;
+
+    Ok(())
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
 
 use crate::state::*;
+use crate::errors::PresaleError;
 
 #[derive(Accounts)]
 #[instruction(pda_bump: u8)]
@@ -9,7 +10,7 @@ pub struct CollectFunds<'info> {
 
     #[account(
         has_one = payment_treasury,
-        has_one = authority,
+        has_one = treasurer,
         owner = crate::id()
     )]
     pub presale_account: Account<'info, PresaleInfo>,
@@ -20,7 +21,7 @@ pub struct CollectFunds<'info> {
     #[account(
         mut,
         constraint = to_account.mint == payment_treasury.mint,
-        constraint = to_account.owner == authority.key(),
+        constraint = to_account.owner == presale_account.funds_recipient(),
         owner = token::ID,
     )]
     pub to_account: Account<'info, TokenAccount>,
@@ -31,16 +32,28 @@ pub struct CollectFunds<'info> {
     )]
     pub presale_pda: AccountInfo<'info>,
 
-    pub authority: Signer<'info>,
+    pub treasurer: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 
 }
 
 
-pub fn handler(ctx: Context<CollectFunds>, pda_bump: u8) -> ProgramResult {
+// The platform fee account is passed in `remaining_accounts` when the presale pays a fee
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CollectFunds<'info>>, pda_bump: u8) -> ProgramResult {
+    // Beneficiaries can only be paid their shares through `distribute_funds`
+    if !ctx.accounts.presale_account.beneficiaries.is_empty() {
+        return Err(PresaleError::FundsAreSplit.into());
+    }
 
-    let amount_to_collect = ctx.accounts.payment_treasury.amount;
+    let amount_to_collect = pay_platform_fee(
+        &ctx.accounts.presale_account,
+        &ctx.accounts.payment_treasury,
+        ctx.remaining_accounts,
+        ctx.accounts.presale_pda.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        pda_bump
+    )?;
     if amount_to_collect > 0 {
         token::transfer(
             CpiContext::new_with_signer(
@@ -57,4 +70,41 @@ pub fn handler(ctx: Context<CollectFunds>, pda_bump: u8) -> ProgramResult {
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Pays the platform its share of the treasury out of the first of `remaining_accounts`, which must be
+// the fee account recorded on the presale when it has a fee. Returns what is left for the presale
+pub fn pay_platform_fee<'info>(
+    presale_account: &Account<'info, PresaleInfo>,
+    payment_treasury: &Account<'info, TokenAccount>,
+    remaining_accounts: &[AccountInfo<'info>],
+    presale_pda: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    pda_bump: u8
+) -> Result<u64, ProgramError> {
+    let total_amount = payment_treasury.amount;
+    if presale_account.fee_bps == 0 {
+        return Ok(total_amount);
+    }
+
+    let fee_account = match remaining_accounts.first() {
+        Some(info) if info.key() == presale_account.fee_account && info.is_writable => info,
+        _ => return Err(PresaleError::InvalidFeeAccount.into()),
+    };
+    let fee = basis_points_of(total_amount, presale_account.fee_bps)?;
+    if fee > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program, 
+                Transfer {
+                    from: payment_treasury.to_account_info(),
+                    to: fee_account.clone(),
+                    authority: presale_pda
+                },
+                &[&[b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref(), &[pda_bump]]]
+            ), 
+            fee
+        )?;
+    }
+    Ok(total_amount - fee)
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ConfigureEarlyExit<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<ConfigureEarlyExit>, penalty_bps: u16, penalty_to_pool: bool) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Buyers must know the price of leaving before they can purchase
+    if presale_account.started {
+        return Err(PresaleError::PresaleAlreadyStarted.into());
+    }
+
+    // The penalty shrinks towards `vesting_end`, which lots and milestones don't have.
+    // Buyers who were paid a bonus to stay locked up shouldn't be able to leave either
+    if presale_account.lot_duration > 0
+        || !presale_account.milestones.is_empty()
+        || !presale_account.lockup_options.is_empty()
+    {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+    if penalty_bps as u64 > BASIS_POINTS {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    presale_account.early_exit_penalty_bps = penalty_bps;
+    presale_account.penalty_to_pool = penalty_to_pool;
+    Ok(())
+}
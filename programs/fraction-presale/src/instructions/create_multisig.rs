@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(multisig_bump: u8)]
+pub struct CreateMultisig<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    // Takes over as the presale authority, signing for it once enough owners approve a transaction
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MULTISIG_SIZE,
+        seeds = [b"multisig".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = multisig_bump,
+    )]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<CreateMultisig>, owners: Vec<Pubkey>, threshold: u8) -> ProgramResult {
+    // Handing over the presale is an authority change like any other, which the timelock must not let through
+    if ctx.accounts.presale_account.timelock_delay > 0 {
+        return Err(PresaleError::TimelockActive.into());
+    }
+
+    Multisig::validate_owners(&owners, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.presale_account = ctx.accounts.presale_account.key();
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.owner_set_seqno = 0;
+
+    // The PDA is derived rather than typed in, so it can take over without going through `accept_authority`.
+    // It takes every role as well, so none of them is left with the key the multisig replaces
+    let multisig_key = multisig.key();
+    let presale_account = &mut ctx.accounts.presale_account;
+    presale_account.authority = multisig_key;
+    presale_account.pending_authority = Pubkey::default();
+    presale_account.set_roles(multisig_key, multisig_key, multisig_key);
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::collect_funds::pay_platform_fee;
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8)]
+pub struct DistributeFunds<'info> {
+
+    #[account(
+        has_one = payment_treasury,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    #[account(mut)]
+    pub payment_treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+// The beneficiaries' token accounts are passed in `remaining_accounts` in the order they are listed,
+// after the platform fee account when the presale pays a fee
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, DistributeFunds<'info>>, pda_bump: u8) -> ProgramResult {
+    let beneficiaries = &ctx.accounts.presale_account.beneficiaries;
+    if beneficiaries.is_empty() {
+        return Err(PresaleError::NoBeneficiaries.into());
+    }
+
+    let total_amount = pay_platform_fee(
+        &ctx.accounts.presale_account,
+        &ctx.accounts.payment_treasury,
+        ctx.remaining_accounts,
+        ctx.accounts.presale_pda.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        pda_bump
+    )?;
+    let beneficiary_accounts = if ctx.accounts.presale_account.fee_bps > 0 { &ctx.remaining_accounts[1..] } else { ctx.remaining_accounts };
+    if beneficiary_accounts.len() != beneficiaries.len() {
+        return Err(PresaleError::InvalidBeneficiaryAccounts.into());
+    }
+
+    if total_amount == 0 {
+        return Ok(());
+    }
+
+    // Every share is rounded down, and what is left over goes to the first beneficiary
+    let mut shares: Vec<u64> = Vec::with_capacity(beneficiaries.len());
+    for beneficiary in beneficiaries.iter() {
+        shares.push(basis_points_of(total_amount, beneficiary.share_bps)?);
+    }
+    let distributed: u64 = shares.iter().sum();
+    shares[0] += total_amount - distributed;
+
+    let presale_key = ctx.accounts.presale_account.key();
+    let presale_seeds: &[&[u8]] = &[b"presale".as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[pda_bump]];
+
+    for ((beneficiary, to_account_info), share) in beneficiaries.iter().zip(beneficiary_accounts.iter()).zip(shares) {
+        if to_account_info.key() != beneficiary.token_account || !to_account_info.is_writable {
+            return Err(PresaleError::InvalidBeneficiaryAccounts.into());
+        }
+        let to_account: Account<'info, TokenAccount> = Account::try_from(to_account_info)?;
+        if to_account.mint != ctx.accounts.payment_treasury.mint {
+            return Err(PresaleError::InvalidBeneficiaryAccounts.into());
+        }
+        if share == 0 {
+            continue;
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                Transfer {
+                    from: ctx.accounts.payment_treasury.to_account_info(),
+                    to: to_account_info.clone(),
+                    authority: ctx.accounts.presale_pda.to_account_info()
+                },
+                &[presale_seeds]
+            ), 
+            share
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::clock::Clock;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::remove_fractions::withdraw_fractions;
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8)]
+pub struct ExecuteAction<'info> {
+
+    #[account(
+        mut,
+        has_one = fraction_treasury,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    #[account(
+        mut,
+        has_one = presale_account,
+        has_one = payer,
+        close = payer,
+        owner = crate::id()
+    )]
+    pub queued_action: Box<Account<'info, QueuedAction>>,
+
+    #[account(mut)]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    // The presale authority, the treasurer for beneficiary changes, or the operator for treasury fractions
+    #[account(constraint = authority.key() == queued_action.action.role(&presale_account))]
+    pub authority: Signer<'info>,
+
+    // Gets back the rent it paid when the action was queued
+    #[account(mut)]
+    pub payer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+// Removing fractions takes the operator's fraction account as the only remaining account
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteAction<'info>>, pda_bump: u8) -> ProgramResult {
+    // The delay is the one in force now, so lengthening it also holds back actions that were already queued
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let executable_at = match ctx.accounts.queued_action.queued_at.checked_add(ctx.accounts.presale_account.timelock_delay) {
+        Some(val) => val,
+        None => return Err(PresaleError::NumericalOverflowError.into()),
+    };
+    if current_timestamp < executable_at {
+        return Err(PresaleError::TimelockNotExpired.into());
+    }
+
+    match ctx.accounts.queued_action.action.clone() {
+        AdminAction::RemoveFractions { amount } => {
+            let to_account_info = match ctx.remaining_accounts.first() {
+                Some(info) if info.is_writable => info,
+                _ => return Err(PresaleError::InvalidActionAccounts.into()),
+            };
+            let to_account: Account<'info, TokenAccount> = Account::try_from(to_account_info)?;
+            if to_account.mint != ctx.accounts.fraction_treasury.mint || to_account.owner != ctx.accounts.presale_account.operator {
+                return Err(PresaleError::InvalidActionAccounts.into());
+            }
+
+            withdraw_fractions(
+                &ctx.accounts.presale_account,
+                &ctx.accounts.fraction_treasury,
+                to_account_info.clone(),
+                ctx.accounts.presale_pda.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                pda_bump,
+                amount
+            )?;
+        }
+        AdminAction::UpdatePresaleParams { price, max_amount, presale_end, vesting_end } => {
+            ctx.accounts.presale_account.update_params(price, max_amount, presale_end, vesting_end, current_timestamp)?;
+        }
+        AdminAction::SetFundsDestination { funds_destination } => {
+            ctx.accounts.presale_account.funds_destination = funds_destination;
+        }
+        AdminAction::SetTimelockDelay { timelock_delay } => {
+            ctx.accounts.presale_account.timelock_delay = timelock_delay;
+        }
+        AdminAction::SetRoles { operator, treasurer, pauser } => {
+            ctx.accounts.presale_account.set_roles(operator, treasurer, pauser);
+        }
+        AdminAction::SetBeneficiaries { beneficiaries } => {
+            ctx.accounts.presale_account.beneficiaries = beneficiaries;
+        }
+        AdminAction::ApproveGrants { amount } => {
+            let presale_account = &mut ctx.accounts.presale_account;
+            presale_account.grant_allowance = match presale_account.grant_allowance.checked_add(amount) {
+                Some(val) => val,
+                None => return Err(PresaleError::NumericalOverflowError.into()),
+            };
+        }
+    }
+
+    Ok(())
+}
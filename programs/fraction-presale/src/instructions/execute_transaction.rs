@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke_signed};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(multisig_bump: u8)]
+pub struct ExecuteTransaction<'info> {
+
+    // Not writable here, so `set_multisig_owners` can update it through the executed instruction
+    #[account(
+        seeds = [b"multisig".as_ref(), multisig.presale_account.as_ref(), crate::id().as_ref()],
+        bump = multisig_bump,
+        owner = crate::id()
+    )]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    // Closed once executed, its rent going back to whoever proposed it
+    #[account(
+        mut,
+        close = proposer,
+        has_one = multisig,
+        has_one = proposer,
+        owner = crate::id()
+    )]
+    pub transaction: Box<Account<'info, MultisigTransaction>>,
+
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    pub executor: Signer<'info>,
+
+}
+
+
+// The executed instruction's accounts, including the program it targets, are passed in `remaining_accounts`
+pub(crate) fn handler(ctx: Context<ExecuteTransaction>, multisig_bump: u8) -> ProgramResult {
+    let multisig = &ctx.accounts.multisig;
+    multisig.owner_index(ctx.accounts.executor.key)?;
+
+    let transaction = &mut ctx.accounts.transaction;
+    if transaction.owner_set_seqno != multisig.owner_set_seqno {
+        return Err(PresaleError::MultisigOwnersChanged.into());
+    }
+    if transaction.did_execute {
+        return Err(PresaleError::TransactionAlreadyExecuted.into());
+    }
+    let approvals = transaction.signers.iter().filter(|signed| **signed).count();
+    if approvals < multisig.threshold as usize {
+        return Err(PresaleError::NotEnoughApprovals.into());
+    }
+
+    // Written out before the call so the instruction can't execute this transaction again
+    transaction.did_execute = true;
+    transaction.exit(ctx.program_id)?;
+
+    let multisig_key = multisig.key();
+    let instruction = Instruction {
+        program_id: transaction.program_id,
+        accounts: transaction.accounts.iter().map(|account| AccountMeta {
+            pubkey: account.pubkey,
+            is_signer: account.is_signer || account.pubkey == multisig_key,
+            is_writable: account.is_writable,
+        }).collect(),
+        data: transaction.data.clone(),
+    };
+
+    let presale_key = multisig.presale_account;
+    let multisig_seeds: &[&[&[u8]]] = &[&[b"multisig".as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[multisig_bump]]];
+    invoke_signed(&instruction, ctx.remaining_accounts, multisig_seeds)?;
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Transfer, Token, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(vesting_pda_bump: u8, presale_pda_bump: u8)]
+pub struct ExitVestingEarly<'info> {
+
+    #[account(
+        mut,
+        has_one = fraction_treasury,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    // Penalised fractions are returned here, either for resale or held for the remaining buyers
+    #[account(mut)]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = to_account.mint == fraction_treasury.mint,
+        constraint = to_account.owner == signer.key(),
+    )]
+    pub to_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vesting_account.mint == fraction_treasury.mint,
+        constraint = vesting_account.owner == user_vesting_pda.key() || vesting_account.key() == presale_account.vesting_escrow,
+    )]
+    pub vesting_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting".as_ref(), user_vesting_pda.signer.as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = vesting_pda_bump,
+        has_one = vesting_account,
+        owner = crate::id()
+    )]
+    pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = presale_pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    // The position's owner, or the holder of its receipt when it is tokenized
+    pub signer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExitVestingEarly<'info>>, vesting_pda_bump: u8, presale_pda_bump: u8) -> ProgramResult {
+
+    let receipt = ctx.accounts.user_vesting_pda.authorize(ctx.remaining_accounts, ctx.accounts.signer.key)?;
+
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    if presale_account.early_exit_penalty_bps == 0 {
+        return Err(PresaleError::EarlyExitDisabled.into());
+    }
+
+    // Revocable grants would escape the authority's clawback
+    if ctx.accounts.user_vesting_pda.revocable_amount > 0 {
+        return Err(PresaleError::GrantIsRevocable.into());
+    }
+
+    let locked_amount = ctx.accounts.user_vesting_pda.locked_amount();
+    if locked_amount == 0 {
+        return Err(PresaleError::VestingAccountIsEmpty.into());
+    }
+
+    // Once vesting is over the fractions can simply be unlocked
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let penalty_bps = presale_account.early_exit_penalty_bps_at(current_timestamp)?;
+    if penalty_bps == 0 {
+        return Err(PresaleError::VestingPeriodFinished.into());
+    }
+
+    // Only the part that hasn't vested yet is penalised
+    let user_vesting_pda = &mut ctx.accounts.user_vesting_pda;
+    let vested = user_vesting_pda.vested_amount(presale_account, current_timestamp)?;
+    let unvested = user_vesting_pda.total_amount.saturating_sub(vested);
+    let penalty = basis_points_of(unvested, penalty_bps)?;
+    let amount_to_release = locked_amount - penalty;
+
+    // Penalties redistributed while this buyer was vesting are still theirs
+    user_vesting_pda.settle_penalty_rewards(presale_account)?;
+    let penalty_rewards = user_vesting_pda.penalty_rewards;
+    user_vesting_pda.penalty_rewards = 0;
+    user_vesting_pda.claimed_amount = user_vesting_pda.total_amount;
+    user_vesting_pda.penalty_debt = 0;
+
+    presale_account.release_vesting(locked_amount, penalty_rewards);
+
+    // The penalty goes back up for sale unless it is shared among the buyers still vesting
+    if presale_account.penalty_to_pool {
+        presale_account.redistribute_penalty(penalty)?;
+    }
+
+    // Pooled fractions are held by the presale PDA rather than by the position
+    let position_key = ctx.accounts.user_vesting_pda.signer;
+    let presale_key = ctx.accounts.presale_account.key();
+    let vesting_seeds: &[&[u8]] = &[b"vesting".as_ref(), position_key.as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[vesting_pda_bump]];
+    let presale_seeds: &[&[u8]] = &[b"presale".as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[presale_pda_bump]];
+    let (vesting_authority, vesting_signer) = if ctx.accounts.presale_account.is_vesting_pooled() {
+        (ctx.accounts.presale_pda.to_account_info(), presale_seeds)
+    } else {
+        (ctx.accounts.user_vesting_pda.to_account_info(), vesting_seeds)
+    };
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(), 
+            Transfer {
+                from: ctx.accounts.vesting_account.to_account_info(),
+                to: ctx.accounts.to_account.to_account_info(),
+                authority: vesting_authority.clone()
+            },
+            &[vesting_signer]
+        ), 
+        amount_to_release
+    )?;
+
+    if penalty > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                Transfer {
+                    from: ctx.accounts.vesting_account.to_account_info(),
+                    to: ctx.accounts.fraction_treasury.to_account_info(),
+                    authority: vesting_authority.clone()
+                },
+                &[vesting_signer]
+            ), 
+            penalty
+        )?;
+    }
+
+    if penalty_rewards > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                Transfer {
+                    from: ctx.accounts.fraction_treasury.to_account_info(),
+                    to: ctx.accounts.to_account.to_account_info(),
+                    authority: ctx.accounts.presale_pda.to_account_info()
+                },
+                &[presale_seeds]
+            ), 
+            penalty_rewards
+        )?;
+    }
+
+    // The receipt goes once nothing is left to claim, and the position takes in no more fractions after that
+    if let Some((receipt_mint, receipt_account)) = receipt {
+        if ctx.accounts.user_vesting_pda.locked_amount() == 0 {
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(), 
+                    Burn {
+                        mint: receipt_mint,
+                        to: receipt_account.to_account_info(),
+                        authority: ctx.accounts.signer.to_account_info()
+                    }
+                ), 
+                1
+            )?;
+        }
+    }
+
+    Ok(())
+}
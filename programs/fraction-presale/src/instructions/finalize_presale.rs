@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::clock::Clock;
+use anchor_spl::token::{self, Burn, Transfer, Token, Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(pda_bump: u8)]
+pub struct FinalizePresale<'info> {
+
+    #[account(
+        mut,
+        has_one = fraction_treasury,
+        has_one = fraction_mint,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    #[account(mut)]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+// When unsold fractions are returned, their destination is passed in `remaining_accounts`
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, FinalizePresale<'info>>, pda_bump: u8) -> ProgramResult {
+    let presale_account = &ctx.accounts.presale_account;
+
+    if presale_account.finalized {
+        return Err(PresaleError::PresaleAlreadyFinalized.into());
+    }
+    // A presale that was never started is over once its end has passed as well
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    if current_timestamp < presale_account.presale_end {
+        return Err(PresaleError::PresaleNotFinished.into());
+    }
+
+    // Penalties owed to buyers stay in the treasury
+    let unsold_amount = presale_account.available_fractions(ctx.accounts.fraction_treasury.amount);
+    release_unsold_fractions(
+        presale_account,
+        &ctx.accounts.fraction_treasury,
+        ctx.accounts.fraction_mint.to_account_info(),
+        ctx.remaining_accounts,
+        ctx.accounts.presale_pda.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        pda_bump,
+        unsold_amount
+    )?;
+
+    ctx.accounts.presale_account.finalized = true;
+    Ok(())
+}
+
+// Burns `amount` out of `from_account`, the treasury or the pooled escrow, or returns it to `unsold_destination`,
+// which is then the first of `remaining_accounts`. Also used by `close_presale` for what is left after finalizing
+#[allow(clippy::too_many_arguments)]
+pub fn release_unsold_fractions<'info>(
+    presale_account: &Account<'info, PresaleInfo>,
+    from_account: &Account<'info, TokenAccount>,
+    fraction_mint: AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    presale_pda: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    pda_bump: u8,
+    amount: u64
+) -> ProgramResult {
+    let presale_key = presale_account.key();
+    let program_id = crate::id();
+    let presale_seeds: &[&[u8]] = &[b"presale".as_ref(), presale_key.as_ref(), program_id.as_ref(), &[pda_bump]];
+
+    if presale_account.unsold_destination == Pubkey::default() {
+        if amount > 0 {
+            token::burn(
+                CpiContext::new_with_signer(
+                    token_program, 
+                    Burn {
+                        mint: fraction_mint,
+                        to: from_account.to_account_info(),
+                        authority: presale_pda
+                    },
+                    &[presale_seeds]
+                ), 
+                amount
+            )?;
+        }
+    } else {
+        let destination = match remaining_accounts.first() {
+            Some(info) if info.key() == presale_account.unsold_destination && info.is_writable => info,
+            _ => return Err(PresaleError::InvalidUnsoldDestination.into()),
+        };
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program, 
+                    Transfer {
+                        from: from_account.to_account_info(),
+                        to: destination.clone(),
+                        authority: presale_pda
+                    },
+                    &[presale_seeds]
+                ), 
+                amount
+            )?;
+        }
+    }
+    Ok(())
+}
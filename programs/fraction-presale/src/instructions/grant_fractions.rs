@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+use anchor_lang::solana_program::sysvar::clock::Clock;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(presale_pda_bump: u8, vesting_pda_bump: u8)]
+pub struct GrantFractions<'info> {
+
+    #[account(
+        mut,
+        has_one = fraction_treasury,
+        has_one = operator,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    #[account(mut)]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vesting_account.mint == fraction_treasury.mint,
+        constraint = vesting_account.owner == user_vesting_pda.key() || vesting_account.key() == presale_account.vesting_escrow,
+    )]
+    pub vesting_account: Box<Account<'info, TokenAccount>>,
+
+    // The recipient opens their vesting position like any buyer would
+    #[account(
+        mut,
+        seeds = [b"vesting".as_ref(), user_vesting_pda.signer.as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = vesting_pda_bump,
+        has_one = vesting_account,
+        owner = crate::id()
+    )]
+    pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = presale_pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    pub operator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, GrantFractions<'info>>, presale_pda_bump: u8, amount: u64, revocable: bool) -> ProgramResult {
+    if amount == 0 { return Err(PresaleError::AmountIsZero.into()); }
+
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Lots vest on their own clocks, so there is no single schedule to tell the unvested part of a grant by
+    if revocable && presale_account.lot_duration > 0 {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    if ctx.accounts.user_vesting_pda.is_receipt_burned() {
+        return Err(PresaleError::VestingReceiptBurned.into());
+    }
+
+    if presale_account.available_fractions(ctx.accounts.fraction_treasury.amount) < amount {
+        return Err(PresaleError::NotEnoughTokensInFractionTreasury.into());
+    }
+    presale_account.spend_grant_allowance(amount)?;
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(), 
+            Transfer {
+                from: ctx.accounts.fraction_treasury.to_account_info(),
+                to: ctx.accounts.vesting_account.to_account_info(),
+                authority: ctx.accounts.presale_pda.to_account_info()
+            },
+            &[&[b"presale".as_ref(), presale_account.key().as_ref(), ctx.program_id.as_ref(), &[presale_pda_bump]]]
+        ), 
+        amount
+    )?;
+
+    // Grants vest exactly like purchases, lots included
+    if presale_account.lot_duration > 0 {
+        let current_timestamp = Clock::get()?.unix_timestamp as u64;
+        let mut vesting_lots = VestingLots::load(ctx.remaining_accounts, &ctx.accounts.user_vesting_pda.key())?;
+        vesting_lots.push_lot(amount, current_timestamp, presale_account.lot_duration)?;
+        vesting_lots.exit(ctx.program_id)?;
+    }
+
+    let user_vesting_pda = &mut ctx.accounts.user_vesting_pda;
+    presale_account.vest(user_vesting_pda, amount)?;
+    if revocable {
+        user_vesting_pda.revocable_amount = match user_vesting_pda.revocable_amount.checked_add(amount) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+    }
+
+    Ok(())
+}
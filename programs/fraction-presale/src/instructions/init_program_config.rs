@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(config_bump: u8)]
+pub struct InitProgramConfig<'info> {
+
+    #[account(
+        init,
+        payer = upgrade_authority,
+        space = 8 + PROGRAM_CONFIG_SIZE,
+        seeds = [b"config".as_ref()],
+        bump = config_bump,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    // Holds the upgrade authority of this program, which is checked in the handler
+    pub program_data: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<InitProgramConfig>, config_bump: u8, admin: Pubkey, fee_bps: u16) -> ProgramResult {
+    // Anyone could create the config first otherwise, and pick the admin
+    let (program_data_key, _) = Pubkey::find_program_address(&[ctx.program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let program_data = &ctx.accounts.program_data;
+    if program_data.key() != program_data_key || *program_data.owner != bpf_loader_upgradeable::id() {
+        return Err(PresaleError::NotUpgradeAuthority.into());
+    }
+    let upgrade_authority = match bincode::deserialize(&program_data.try_borrow_data()?) {
+        Ok(UpgradeableLoaderState::ProgramData { upgrade_authority_address, .. }) => upgrade_authority_address,
+        _ => return Err(PresaleError::NotUpgradeAuthority.into()),
+    };
+    if upgrade_authority != Some(ctx.accounts.upgrade_authority.key()) {
+        return Err(PresaleError::NotUpgradeAuthority.into());
+    }
+
+    if fee_bps as u64 > MAX_FEE_BPS {
+        return Err(PresaleError::InvalidFee.into());
+    }
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.admin = admin;
+    program_config.fee_bps = fee_bps;
+    program_config.bump = config_bump;
+    Ok(())
+}
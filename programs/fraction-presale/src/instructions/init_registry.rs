@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(registry_bump: u8)]
+pub struct InitRegistry<'info> {
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + REGISTRY_SIZE,
+        seeds = [b"registry".as_ref()],
+        bump = registry_bump,
+    )]
+    pub registry: Box<Account<'info, Registry>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<InitRegistry>, registry_bump: u8) -> ProgramResult {
+    let registry = &mut ctx.accounts.registry;
+    registry.presale_count = 0;
+    registry.bump = registry_bump;
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_spl::token::{Token, Mint, TokenAccount};
 
 use crate::state::*;
 use crate::errors::*;
@@ -53,9 +53,14 @@ pub struct InitVesting<'info> {
 
 }
 
-pub fn handler(ctx: Context<InitVesting>) -> ProgramResult {
-    let user_vesting_pda = &mut ctx.accounts.user_vesting_pda;
-    user_vesting_pda.signer = ctx.accounts.signer.key();
-    user_vesting_pda.vesting_account = ctx.accounts.vesting_account.key();
+pub(crate) fn handler(ctx: Context<InitVesting>) -> ProgramResult {
+    // Pooled presales have no per-buyer token account, their positions are opened by the first purchase
+    if ctx.accounts.presale_account.is_vesting_pooled() {
+        return Err(PresaleError::VestingIsPooled.into());
+    }
+
+    let vesting_account_key = ctx.accounts.vesting_account.key();
+    ctx.accounts.user_vesting_pda.open(ctx.accounts.signer.key(), vesting_account_key);
+    ctx.accounts.presale_account.position_opened()?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, Mint, TokenAccount};
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(presale_pda_bump: u8, escrow_bump: u8)]
+pub struct InitVestingEscrow<'info> {
+
+    #[account(
+        mut,
+        has_one = fraction_mint,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    // Holds the fractions of every vesting position, so buyers don't pay rent for a token account each
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"escrow".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = escrow_bump,
+        token::mint = fraction_mint,
+        token::authority = presale_pda
+    )]
+    pub vesting_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = presale_pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
+    pub authority: Signer<'info>,
+
+    // Pays for the escrow, so the authority can be a PDA such as the multisig
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<InitVestingEscrow>) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Buyers must not have opened their own vesting accounts yet
+    if presale_account.started {
+        return Err(PresaleError::PresaleAlreadyStarted.into());
+    }
+
+    // Positions opened by grants or airdrops before the start already hold their fractions themselves
+    if presale_account.open_positions > 0 {
+        return Err(PresaleError::PositionsStillOpen.into());
+    }
+
+    presale_account.vesting_escrow = ctx.accounts.vesting_escrow.key();
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(vesting_pda_bump: u8, lots_pda_bump: u8)]
+pub struct InitVestingLots<'info> {
+
+    #[account(owner = crate::id())]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    #[account(
+        seeds = [b"vesting".as_ref(), signer.key().as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = vesting_pda_bump,
+        has_one = signer,
+        owner = crate::id()
+    )]
+    pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
+
+    // Every purchase made through `user_vesting_pda` is recorded here as a separate lot
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VESTING_LOTS_SIZE,
+        seeds = [b"lots".as_ref(), signer.key().as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = lots_pda_bump,
+    )]
+    pub vesting_lots: Box<Account<'info, VestingLots>>,
+
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+pub(crate) fn handler(ctx: Context<InitVestingLots>) -> ProgramResult {
+    if ctx.accounts.presale_account.lot_duration == 0 {
+        return Err(PresaleError::LotVestingDisabled.into());
+    }
+
+    let vesting_lots = &mut ctx.accounts.vesting_lots;
+    vesting_lots.vesting_info = ctx.accounts.user_vesting_pda.key();
+    vesting_lots.lots = Vec::new();
+    Ok(())
+}
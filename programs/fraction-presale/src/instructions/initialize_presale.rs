@@ -1,20 +1,40 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_spl::token::{Token, Mint, TokenAccount};
 
 use crate::state::*;
+use crate::errors::*;
 
 #[derive(Accounts)]
-#[instruction(pda_bump: u8)]
+#[instruction(pda_bump: u8, presale_bump: u8, presale_index_bump: u8, presale_index_page: u32)]
 pub struct InitializePresale<'info> {
 
-    // This accounts contains all the information about the presale
+    // This accounts contains all the information about the presale, numbered by the registry
     #[account(
         init,
         payer = authority,
-        space = 8 + PRESALE_INFO_SIZE
+        space = 8 + PRESALE_INFO_SIZE,
+        seeds = [b"presale".as_ref(), fraction_mint.key().as_ref(), &registry.presale_count.to_le_bytes()],
+        bump = presale_bump,
     )]
     pub presale_account: Box<Account<'info, PresaleInfo>>,
 
+    #[account(
+        mut,
+        seeds = [b"registry".as_ref()],
+        bump = registry.bump,
+    )]
+    pub registry: Box<Account<'info, Registry>>,
+
+    // Created along with the first presale of the fraction mint to go into the page
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PRESALE_INDEX_SIZE,
+        seeds = [b"presale_index".as_ref(), fraction_mint.key().as_ref(), &presale_index_page.to_le_bytes()],
+        bump = presale_index_bump,
+    )]
+    pub presale_index: Box<Account<'info, PresaleIndex>>,
+
     // This account will hold the access tokens used for purchases
     #[account(
         init, 
@@ -56,6 +76,12 @@ pub struct InitializePresale<'info> {
 
     pub access_mint: Box<Account<'info, Mint>>,
 
+    // Fraction account `finalize_presale` returns unsold fractions to, or the system program to have them burned
+    pub unsold_destination: AccountInfo<'info>,
+
+    // The platform fee in force now applies for the presale's whole life, there is none until the config exists
+    pub program_config: AccountInfo<'info>,
+
     // Authority for the presale account
     pub authority: Signer<'info>,
 
@@ -67,14 +93,103 @@ pub struct InitializePresale<'info> {
 
 }
 
-pub fn handler(
-    ctx: Context<InitializePresale>,
+#[allow(clippy::too_many_arguments)]
+// When there is a platform fee, the `FeeRecipient` for the payment mint is passed first in `remaining_accounts`
+pub(crate) fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializePresale<'info>>,
+    presale_index_page: u32,
     price: u64,
     max_amount: u64,
     presale_end: u64,
-    vesting_end: u64
+    vesting_end: u64,
+    lot_duration: u64,
+    tge_timestamp: u64,
+    tge_unlock_bps: u16,
+    vesting_start_deadline: u64,
+    vesting_duration: u64
 ) -> ProgramResult {
 
+    if tge_unlock_bps as u64 > BASIS_POINTS {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    if vesting_start_deadline > 0 {
+        // Deferred vesting leaves every vesting timestamp unset until `set_vesting_start`
+        if vesting_end != 0 || tge_timestamp != 0 || lot_duration > 0 || vesting_start_deadline < presale_end {
+            return Err(PresaleError::InvalidVestingSchedule.into());
+        }
+    } else if tge_unlock_bps > 0 && (lot_duration > 0 || tge_timestamp > vesting_end) {
+        // The TGE share is taken out of the global schedule, so it can't be mixed with vesting lots
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    let unsold_destination = ctx.accounts.unsold_destination.key();
+    if unsold_destination != Pubkey::default() {
+        let unsold_destination_account: Account<'info, TokenAccount> = Account::try_from(&ctx.accounts.unsold_destination)?;
+        if unsold_destination_account.mint != ctx.accounts.fraction_mint.key() {
+            return Err(PresaleError::InvalidUnsoldDestination.into());
+        }
+    }
+
+    let program_config_info = &ctx.accounts.program_config;
+    let (program_config_key, _) = Pubkey::find_program_address(&[b"config".as_ref()], ctx.program_id);
+    if program_config_info.key() != program_config_key {
+        return Err(PresaleError::InvalidProgramConfig.into());
+    }
+    let fee_bps = if program_config_info.owner == ctx.program_id {
+        let program_config: Account<'info, ProgramConfig> = Account::try_from(program_config_info)?;
+        program_config.fee_bps
+    } else {
+        0
+    };
+    let fee_account = if fee_bps > 0 {
+        let fee_recipient_info = match ctx.remaining_accounts.first() {
+            Some(info) => info,
+            None => return Err(PresaleError::InvalidFeeAccount.into()),
+        };
+        let fee_recipient: Account<'info, FeeRecipient> = Account::try_from(fee_recipient_info)?;
+        if fee_recipient.payment_mint != ctx.accounts.payment_mint.key() {
+            return Err(PresaleError::InvalidFeeAccount.into());
+        }
+        fee_recipient.token_account
+    } else {
+        Pubkey::default()
+    };
+
+    // Pages are opened in order, each once the one before it is full, so clients find every presale of the
+    // mint by reading pages until one is missing. The previous page is passed as the last of `remaining_accounts`
+    let fraction_mint_key = ctx.accounts.fraction_mint.key();
+    let presale_index = &mut ctx.accounts.presale_index;
+    if presale_index.fraction_mint == Pubkey::default() {
+        if presale_index_page > 0 {
+            let previous_page_info = match ctx.remaining_accounts.last() {
+                Some(info) => info,
+                None => return Err(PresaleError::InvalidPresaleIndex.into()),
+            };
+            let previous_page: Account<'info, PresaleIndex> = Account::try_from(previous_page_info)?;
+            if previous_page.fraction_mint != fraction_mint_key || previous_page.page != presale_index_page - 1 {
+                return Err(PresaleError::InvalidPresaleIndex.into());
+            }
+            if previous_page.presales.len() < MAX_INDEXED_PRESALES {
+                return Err(PresaleError::PresaleIndexPageNotFull.into());
+            }
+        }
+        presale_index.fraction_mint = fraction_mint_key;
+        presale_index.page = presale_index_page;
+    }
+
+    // Callers pass the mint's last page, and open the next one once it is full
+    if presale_index.presales.len() >= MAX_INDEXED_PRESALES {
+        return Err(PresaleError::PresaleIndexFull.into());
+    }
+    presale_index.presales.push(ctx.accounts.presale_account.key());
+
+    let registry = &mut ctx.accounts.registry;
+    registry.presale_count = match registry.presale_count.checked_add(1) {
+        Some(val) => val,
+        None => return Err(PresaleError::NumericalOverflowError.into()),
+    };
+
     let presale_account = &mut ctx.accounts.presale_account;
 
     // Set public keys for the important accounts this presale account works with
@@ -87,11 +202,40 @@ pub fn handler(
     // Set presale params
     presale_account.presale_end = presale_end;
     presale_account.vesting_end = vesting_end;
+    presale_account.lot_duration = lot_duration;
+    presale_account.tge_timestamp = tge_timestamp;
+    presale_account.tge_unlock_bps = tge_unlock_bps;
+    presale_account.vesting_start = 0;
+    presale_account.vesting_start_deadline = vesting_start_deadline;
+    presale_account.vesting_duration = vesting_duration;
+    presale_account.milestones = Vec::new();
+    presale_account.early_exit_penalty_bps = 0;
+    presale_account.penalty_to_pool = false;
+    presale_account.fractions_vesting = 0;
+    presale_account.penalty_per_fraction = 0;
+    presale_account.penalty_pool = 0;
+    presale_account.lockup_options = Vec::new();
+    presale_account.transfer_requires_authority = false;
+    presale_account.vesting_receipts = false;
+    presale_account.vesting_escrow = Pubkey::default();
+    presale_account.pending_authority = Pubkey::default();
+    presale_account.set_roles(ctx.accounts.authority.key(), ctx.accounts.authority.key(), ctx.accounts.authority.key());
+    presale_account.paused = false;
+    presale_account.timelock_delay = 0;
+    presale_account.funds_destination = Pubkey::default();
+    presale_account.queued_actions = 0;
+    presale_account.beneficiaries = Vec::new();
+    presale_account.fee_bps = fee_bps;
+    presale_account.fee_account = fee_account;
+    presale_account.unsold_destination = unsold_destination;
+    presale_account.finalized = false;
+    presale_account.open_positions = 0;
     presale_account.fractions_sold = 0;
     presale_account.fraction_mint = ctx.accounts.fraction_mint.key();
     presale_account.access_mint = ctx.accounts.access_mint.key();
     presale_account.payment_mint = ctx.accounts.payment_mint.key();
     presale_account.price = price;
+    presale_account.start_price = 0;
     presale_account.max_amount = max_amount;
 
     Ok(())
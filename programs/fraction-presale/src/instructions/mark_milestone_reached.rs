@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct MarkMilestoneReached<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<MarkMilestoneReached>, index: u8) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    let milestone = match presale_account.milestones.get_mut(index as usize) {
+        Some(milestone) => milestone,
+        None => return Err(PresaleError::MilestoneNotFound.into()),
+    };
+    if milestone.reached {
+        return Err(PresaleError::MilestoneAlreadyReached.into());
+    }
+
+    milestone.reached = true;
+    Ok(())
+}
@@ -1,17 +1,95 @@
+pub mod init_program_config;
+pub mod update_program_config;
+pub mod set_fee_recipient;
+pub mod init_registry;
 pub mod initialize_presale;
 pub mod add_fractions;
 pub mod remove_fractions;
 pub mod start_presale;
+pub mod update_presale_params;
+pub mod set_timelock_delay;
+pub mod set_funds_destination;
+pub mod set_roles;
+pub mod set_beneficiaries;
+pub mod pause_presale;
+pub mod resume_presale;
+pub mod queue_action;
+pub mod cancel_action;
+pub mod execute_action;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod create_multisig;
+pub mod set_multisig_owners;
+pub mod propose_transaction;
+pub mod approve_transaction;
+pub mod execute_transaction;
+pub mod set_vesting_start;
+pub mod set_milestones;
+pub mod mark_milestone_reached;
+pub mod configure_early_exit;
+pub mod set_lockup_options;
+pub mod set_transfer_policy;
+pub mod finalize_presale;
+pub mod close_presale;
 pub mod collect_funds;
+pub mod distribute_funds;
+pub mod init_vesting_escrow;
 pub mod init_vesting_account;
+pub mod init_vesting_lots;
 pub mod purchase_fractions;
 pub mod unlock_fractions;
+pub mod exit_vesting_early;
+pub mod grant_fractions;
+pub mod revoke_grant;
+pub mod airdrop_fractions;
+pub mod transfer_vesting_position;
+pub mod set_unlock_delegate;
+pub mod close_vesting_account;
 
+pub use init_program_config::*;
+pub use update_program_config::*;
+pub use set_fee_recipient::*;
+pub use init_registry::*;
 pub use initialize_presale::*;
 pub use add_fractions::*;
 pub use remove_fractions::*;
 pub use start_presale::*;
+pub use update_presale_params::*;
+pub use set_timelock_delay::*;
+pub use set_funds_destination::*;
+pub use set_roles::*;
+pub use set_beneficiaries::*;
+pub use pause_presale::*;
+pub use resume_presale::*;
+pub use queue_action::*;
+pub use cancel_action::*;
+pub use execute_action::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use create_multisig::*;
+pub use set_multisig_owners::*;
+pub use propose_transaction::*;
+pub use approve_transaction::*;
+pub use execute_transaction::*;
+pub use set_vesting_start::*;
+pub use set_milestones::*;
+pub use mark_milestone_reached::*;
+pub use configure_early_exit::*;
+pub use set_lockup_options::*;
+pub use set_transfer_policy::*;
+pub use finalize_presale::*;
+pub use close_presale::*;
 pub use collect_funds::*;
+pub use distribute_funds::*;
+pub use init_vesting_escrow::*;
 pub use init_vesting_account::*;
+pub use init_vesting_lots::*;
 pub use purchase_fractions::*;
-pub use unlock_fractions::*;
\ No newline at end of file
+pub use unlock_fractions::*;
+pub use exit_vesting_early::*;
+pub use grant_fractions::*;
+pub use revoke_grant::*;
+pub use airdrop_fractions::*;
+pub use transfer_vesting_position::*;
+pub use set_unlock_delegate::*;
+pub use close_vesting_account::*;
\ No newline at end of file
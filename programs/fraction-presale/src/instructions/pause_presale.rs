@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct PausePresale<'info> {
+
+    #[account(
+        mut,
+        has_one = pauser,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub pauser: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<PausePresale>) -> ProgramResult {
+    ctx.accounts.presale_account.paused = true;
+    Ok(())
+}
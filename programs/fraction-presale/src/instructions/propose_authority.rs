@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> ProgramResult {
+    // Nothing changes until the new authority proves it holds the key, and a new proposal replaces
+    // any previous one, so a mistyped pubkey can simply be proposed over
+    ctx.accounts.presale_account.pending_authority = new_authority;
+    Ok(())
+}
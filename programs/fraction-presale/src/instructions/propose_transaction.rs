@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ProposeTransaction<'info> {
+
+    #[account(owner = crate::id())]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + MULTISIG_TRANSACTION_SIZE
+    )]
+    pub transaction: Box<Account<'info, MultisigTransaction>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<ProposeTransaction>, target_program_id: Pubkey, transaction_accounts: Vec<TransactionAccount>, data: Vec<u8>) -> ProgramResult {
+    let multisig = &ctx.accounts.multisig;
+    let proposer_index = multisig.owner_index(ctx.accounts.proposer.key)?;
+
+    if transaction_accounts.len() > MAX_TRANSACTION_ACCOUNTS || data.len() > MAX_TRANSACTION_DATA {
+        return Err(PresaleError::TransactionTooLarge.into());
+    }
+
+    // Proposing counts as the proposer's approval
+    let mut signers = vec![false; multisig.owners.len()];
+    signers[proposer_index] = true;
+
+    let transaction = &mut ctx.accounts.transaction;
+    transaction.multisig = multisig.key();
+    transaction.program_id = target_program_id;
+    transaction.accounts = transaction_accounts;
+    transaction.data = data;
+    transaction.signers = signers;
+    transaction.did_execute = false;
+    transaction.owner_set_seqno = multisig.owner_set_seqno;
+    transaction.proposer = ctx.accounts.proposer.key();
+    Ok(())
+}
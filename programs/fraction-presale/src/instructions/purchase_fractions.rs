@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::{self, InitializeAccount, InitializeMint, MintTo, SetAuthority, Transfer, Token, Mint, TokenAccount};
 use anchor_lang::solana_program::sysvar::clock::Clock;
+use spl_token::instruction::AuthorityType;
 
 use crate::state::*;
 use crate::errors::*;
@@ -14,6 +16,7 @@ pub struct PurchaseFractions<'info> {
         has_one = fraction_treasury,
         has_one = payment_treasury,
         has_one = access_treasury,
+        has_one = fraction_mint,
         constraint = presale_account.access_mint == access_account.mint,
         owner = crate::id()
     )]
@@ -35,22 +38,27 @@ pub struct PurchaseFractions<'info> {
     )]
     pub from_account: Box<Account<'info, TokenAccount>>,
 
+    // Opened on the first purchase unless the buyer already called `init_vesting_account`,
+    // in which case the handler checks it belongs to them
     #[account(
-        mut,
-        constraint = vesting_account.mint == fraction_treasury.mint,
-        constraint = vesting_account.owner == user_vesting_pda.key(),
-    )]
-    pub vesting_account: Box<Account<'info, TokenAccount>>,
-
-    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + VESTING_INFO_SIZE,
         seeds = [b"vesting".as_ref(), signer.key().as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
         bump = vesting_pda_bump,
-        has_one = vesting_account,
-        has_one = signer,
-        owner = crate::id()
     )]
     pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
 
+    #[account(
+        init_if_needed,
+        payer = signer,
+        token::mint = fraction_mint,
+        token::authority = user_vesting_pda,
+    )]
+    pub vesting_account: Box<Account<'info, TokenAccount>>,
+
+    pub fraction_mint: Box<Account<'info, Mint>>,
+
     #[account(
         seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
         bump = presale_pda_bump
@@ -60,16 +68,47 @@ pub struct PurchaseFractions<'info> {
     #[account(mut, constraint = access_account.owner == signer.key())]
     pub access_account: Box<Account<'info, TokenAccount>>,
 
+    #[account(mut)]
     pub signer: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
 }
 
 const ACCESS_TOKEN_UNIT: u64 = 1;
 
-pub fn handler(ctx: Context<PurchaseFractions>, presale_pda_bump: u8, amount: u64) -> ProgramResult {
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, PurchaseFractions<'info>>, presale_pda_bump: u8, vesting_pda_bump: u8, amount: u64, lockup_option: u8) -> ProgramResult {
     if amount == 0 { return Err(PresaleError::AmountIsZero.into()); }
+
+    let signer_key = ctx.accounts.signer.key();
+    let vesting_pda_key = ctx.accounts.user_vesting_pda.key();
+    let vesting_account_key = ctx.accounts.vesting_account.key();
+    let user_vesting_pda = &mut ctx.accounts.user_vesting_pda;
+    if user_vesting_pda.signer == Pubkey::default() {
+        user_vesting_pda.open(signer_key, vesting_account_key);
+        ctx.accounts.presale_account.position_opened()?;
+    }
+    if user_vesting_pda.signer != signer_key || user_vesting_pda.owner != signer_key {
+        return Err(PresaleError::NotPositionOwner.into());
+    }
+    if user_vesting_pda.vesting_account != vesting_account_key {
+        return Err(PresaleError::InvalidVestingAccount.into());
+    }
+
+    // `init_if_needed` only sets the mint and authority of token accounts it creates. An existing one must still
+    // only be spendable by the position, or be the escrow that pools every position's fractions
+    let valid_vesting_account = if ctx.accounts.presale_account.is_vesting_pooled() {
+        vesting_account_key == ctx.accounts.presale_account.vesting_escrow
+    } else {
+        ctx.accounts.vesting_account.owner == vesting_pda_key && ctx.accounts.vesting_account.mint == ctx.accounts.fraction_treasury.mint
+    };
+    if !valid_vesting_account {
+        return Err(PresaleError::InvalidVestingAccount.into());
+    }
     
     let presale_account = &mut ctx.accounts.presale_account;
     let fraction_treasury = &ctx.accounts.fraction_treasury;
@@ -81,6 +120,10 @@ pub fn handler(ctx: Context<PurchaseFractions>, presale_pda_bump: u8, amount: u6
         return Err(PresaleError::PresaleHasNotStarted.into());
     }
 
+    if presale_account.paused {
+        return Err(PresaleError::PresalePaused.into());
+    }
+
     // Make sure the presale has not ended yet
     let current_timestamp = Clock::get()?.unix_timestamp as u64;
     if current_timestamp >= presale_account.presale_end {
@@ -96,8 +139,118 @@ pub fn handler(ctx: Context<PurchaseFractions>, presale_pda_bump: u8, amount: u6
         return Err(PresaleError::AmountTooLarge.into());
     }
 
-    // Make sure there are enough fraction tokens still for sale
-    if fraction_treasury.amount < amount {
+    if ctx.accounts.user_vesting_pda.is_receipt_burned() {
+        return Err(PresaleError::VestingReceiptBurned.into());
+    }
+
+    // Tokenized positions get their receipt with the first purchase into them. The receipt mint, at its PDA,
+    // and a new token account for the buyer to hold it are passed as the last two `remaining_accounts`
+    if presale_account.vesting_receipts && !ctx.accounts.user_vesting_pda.is_tokenized() {
+        let accounts = ctx.remaining_accounts;
+        if accounts.len() < 2 {
+            return Err(PresaleError::MissingVestingReceipt.into());
+        }
+        let receipt_mint = &accounts[accounts.len() - 2];
+        let receipt_account = &accounts[accounts.len() - 1];
+
+        let (receipt_mint_key, receipt_mint_bump) = Pubkey::find_program_address(
+            &[b"receipt".as_ref(), vesting_pda_key.as_ref(), ctx.program_id.as_ref()],
+            ctx.program_id
+        );
+        if *receipt_mint.key != receipt_mint_key || !receipt_mint.is_writable || !receipt_account.is_writable || !receipt_account.is_signer {
+            return Err(PresaleError::MissingVestingReceipt.into());
+        }
+
+        let presale_key = presale_account.key();
+        let receipt_mint_seeds: &[&[u8]] = &[b"receipt".as_ref(), vesting_pda_key.as_ref(), ctx.program_id.as_ref(), &[receipt_mint_bump]];
+        let vesting_seeds: &[&[u8]] = &[b"vesting".as_ref(), signer_key.as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[vesting_pda_bump]];
+        let signer = ctx.accounts.signer.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        create_account(&signer, receipt_mint, &system_program, &ctx.accounts.rent, spl_token::state::Mint::LEN, &token::ID, &[receipt_mint_seeds])?;
+        token::initialize_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeMint {
+                    mint: receipt_mint.clone(),
+                    rent: ctx.accounts.rent.to_account_info()
+                }
+            ),
+            0,
+            &vesting_pda_key,
+            None
+        )?;
+
+        create_account(&signer, receipt_account, &system_program, &ctx.accounts.rent, spl_token::state::Account::LEN, &token::ID, &[])?;
+        token::initialize_account(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                InitializeAccount {
+                    account: receipt_account.clone(),
+                    mint: receipt_mint.clone(),
+                    authority: signer.clone(),
+                    rent: ctx.accounts.rent.to_account_info()
+                }
+            )
+        )?;
+
+        // A single token is minted before the mint authority is dropped, so there can only ever be one receipt
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: receipt_mint.clone(),
+                    to: receipt_account.clone(),
+                    authority: ctx.accounts.user_vesting_pda.to_account_info()
+                },
+                &[vesting_seeds]
+            ),
+            1
+        )?;
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: ctx.accounts.user_vesting_pda.to_account_info(),
+                    account_or_mint: receipt_mint.clone()
+                },
+                &[vesting_seeds]
+            ),
+            AuthorityType::MintTokens,
+            None
+        )?;
+
+        // From now on the receipt decides who may unlock, so a delegate registered earlier is dropped
+        ctx.accounts.user_vesting_pda.receipt_mint = receipt_mint_key;
+        ctx.accounts.user_vesting_pda.delegate = Pubkey::default();
+    }
+
+    // Buyers who opt into a longer lockup receive bonus fractions, and are held to it on every purchase
+    let (lockup_multiplier_bps, bonus_bps) = if presale_account.lockup_options.is_empty() {
+        if lockup_option != 0 {
+            return Err(PresaleError::LockupOptionNotFound.into());
+        }
+        (0, 0)
+    } else {
+        let chosen = match presale_account.lockup_options.get(lockup_option as usize) {
+            Some(val) => val,
+            None => return Err(PresaleError::LockupOptionNotFound.into()),
+        };
+        (chosen.multiplier_bps, chosen.bonus_bps)
+    };
+    // Positions opened by a grant or airdrop have no lockup yet, so their first purchase picks it
+    let user_vesting_pda = &ctx.accounts.user_vesting_pda;
+    if user_vesting_pda.lockup_multiplier_bps != 0 && user_vesting_pda.lockup_multiplier_bps != lockup_multiplier_bps {
+        return Err(PresaleError::LockupOptionMismatch.into());
+    }
+    let bonus_amount = basis_points_of(amount, bonus_bps)?;
+    let vested_amount = match amount.checked_add(bonus_amount) {
+        Some(val) => val,
+        None => return Err(PresaleError::NumericalOverflowError.into()),
+    };
+
+    // Make sure there are enough fraction tokens still for sale, bonus included
+    if presale_account.available_fractions(fraction_treasury.amount) < vested_amount {
         return Err(PresaleError::NotEnoughTokensInFractionTreasury.into());
     }
     
@@ -140,7 +293,7 @@ pub fn handler(ctx: Context<PurchaseFractions>, presale_pda_bump: u8, amount: u6
         payment_amount
     )?;
     
-    // User receives fraction tokens into their vesting account
+    // User receives fraction tokens, along with any lockup bonus, into their vesting account
     token::transfer(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(), 
@@ -151,9 +304,28 @@ pub fn handler(ctx: Context<PurchaseFractions>, presale_pda_bump: u8, amount: u6
             },
             &[&[b"presale".as_ref(), presale_account.key().as_ref(), ctx.program_id.as_ref(), &[presale_pda_bump]]]
         ), 
-        amount
+        vested_amount
     )?;
 
+    // Each purchase starts its own vesting clock when the presale uses vesting lots,
+    // which the first purchase opens unless `init_vesting_lots` was called beforehand
+    if presale_account.lot_duration > 0 {
+        let mut vesting_lots = VestingLots::load_or_open(
+            ctx.remaining_accounts,
+            &ctx.accounts.user_vesting_pda.key(),
+            &ctx.accounts.signer.to_account_info(),
+            &presale_account.key(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.rent,
+            ctx.program_id
+        )?;
+        vesting_lots.push_lot(vested_amount, current_timestamp, presale_account.lot_duration)?;
+        vesting_lots.exit(ctx.program_id)?;
+    }
+
+    let user_vesting_pda = &mut ctx.accounts.user_vesting_pda;
+    user_vesting_pda.lockup_multiplier_bps = lockup_multiplier_bps;
+    presale_account.vest(user_vesting_pda, vested_amount)?;
     presale_account.fractions_sold += amount;
 
     Ok(())
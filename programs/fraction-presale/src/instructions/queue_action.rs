@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::clock::Clock;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(action_bump: u8, action: AdminAction)]
+pub struct QueueAction<'info> {
+
+    #[account(
+        mut,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    // Numbered so several actions can be pending at once
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + QUEUED_ACTION_SIZE,
+        seeds = [b"action".as_ref(), presale_account.key().as_ref(), &presale_account.queued_actions.to_le_bytes()],
+        bump = action_bump,
+    )]
+    pub queued_action: Box<Account<'info, QueuedAction>>,
+
+    // The presale authority, the treasurer for beneficiary changes, or the operator for treasury fractions
+    #[account(constraint = authority.key() == action.role(&presale_account))]
+    pub authority: Signer<'info>,
+
+    // Pays for the queued action, so the authority can be a PDA such as the multisig
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<QueueAction>, action: AdminAction) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    match &action {
+        AdminAction::SetTimelockDelay { timelock_delay } if *timelock_delay > MAX_TIMELOCK_DELAY => {
+            return Err(PresaleError::InvalidTimelockDelay.into());
+        }
+        AdminAction::SetBeneficiaries { beneficiaries } => Beneficiary::validate(beneficiaries)?,
+        _ => {}
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let queued_action = &mut ctx.accounts.queued_action;
+    queued_action.presale_account = presale_account.key();
+    queued_action.action = action;
+    queued_action.payer = ctx.accounts.payer.key();
+    queued_action.queued_at = current_timestamp;
+
+    presale_account.queued_actions = match presale_account.queued_actions.checked_add(1) {
+        Some(val) => val,
+        None => return Err(PresaleError::NumericalOverflowError.into()),
+    };
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
 
 use crate::state::*;
 use crate::errors::*;
@@ -9,12 +9,12 @@ use crate::errors::*;
 pub struct RemoveFractions<'info> {
 
     // The constraints on this account ensure that we're dealing with the right treasuries
-    // and that the instruction is being called by the correct authority
+    // and that the instruction is being called by the correct operator
     #[account(
         mut,
         has_one = fraction_treasury,
         has_one = payment_treasury,
-        has_one = authority,
+        has_one = operator,
         owner = crate::id()
     )]
     pub presale_account: Account<'info, PresaleInfo>,
@@ -28,7 +28,7 @@ pub struct RemoveFractions<'info> {
     #[account(
         mut,
         constraint = to_account.mint == fraction_treasury.mint,
-        constraint = to_account.owner == authority.key()
+        constraint = to_account.owner == operator.key()
     )]
     pub to_account: Account<'info, TokenAccount>,
 
@@ -38,35 +38,59 @@ pub struct RemoveFractions<'info> {
     )]
     pub presale_pda: AccountInfo<'info>,
 
-    pub authority: Signer<'info>,
+    pub operator: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 
 }
 
 
-pub fn handler(ctx: Context<RemoveFractions>, pda_bump: u8, amount: u64) -> ProgramResult {
-    if amount == 0 { return Err(PresaleError::AmountIsZero.into()); }
+pub(crate) fn handler(ctx: Context<RemoveFractions>, pda_bump: u8, amount: u64) -> ProgramResult {
+    // Timelocked presales remove fractions through `queue_action`
+    if ctx.accounts.presale_account.timelock_delay > 0 {
+        return Err(PresaleError::TimelockActive.into());
+    }
 
-    let fraction_treasury = &ctx.accounts.fraction_treasury;
+    withdraw_fractions(
+        &ctx.accounts.presale_account,
+        &ctx.accounts.fraction_treasury,
+        ctx.accounts.to_account.to_account_info(),
+        ctx.accounts.presale_pda.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        pda_bump,
+        amount
+    )
+}
 
-    // Make sure there are enough tokens to transfer
-    if fraction_treasury.amount < amount {
+// Takes fractions that are up for sale out of the treasury, also used by queued removals
+pub fn withdraw_fractions<'info>(
+    presale_account: &Account<'info, PresaleInfo>,
+    fraction_treasury: &Account<'info, TokenAccount>,
+    to_account: AccountInfo<'info>,
+    presale_pda: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    pda_bump: u8,
+    amount: u64
+) -> ProgramResult {
+    if amount == 0 { return Err(PresaleError::AmountIsZero.into()); }
+
+    // Make sure there are enough tokens to transfer, without touching penalties owed to buyers
+    if presale_account.available_fractions(fraction_treasury.amount) < amount {
         return Err(PresaleError::NotEnoughTokensInFractionTreasury.into());
     }
 
     token::transfer(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(), 
+            token_program, 
             Transfer {
-                from: ctx.accounts.fraction_treasury.to_account_info(),
-                to: ctx.accounts.to_account.to_account_info(),
-                authority: ctx.accounts.presale_pda.to_account_info()
+                from: fraction_treasury.to_account_info(),
+                to: to_account,
+                authority: presale_pda
             },
-            &[&[b"presale".as_ref(), ctx.accounts.presale_account.key().as_ref(), ctx.program_id.as_ref(), &[pda_bump]]]
+            &[&[b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref(), &[pda_bump]]]
         ), 
         amount
     )?;
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ResumePresale<'info> {
+
+    #[account(
+        mut,
+        has_one = operator,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub operator: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<ResumePresale>) -> ProgramResult {
+    ctx.accounts.presale_account.paused = false;
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer, Token, TokenAccount};
+use anchor_lang::solana_program::sysvar::clock::Clock;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(vesting_pda_bump: u8, presale_pda_bump: u8)]
+pub struct RevokeGrant<'info> {
+
+    #[account(
+        mut,
+        has_one = fraction_treasury,
+        has_one = operator,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    // Revoked fractions go back to the treasury
+    #[account(mut)]
+    pub fraction_treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = vesting_account.mint == fraction_treasury.mint,
+        constraint = vesting_account.owner == user_vesting_pda.key() || vesting_account.key() == presale_account.vesting_escrow,
+    )]
+    pub vesting_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting".as_ref(), user_vesting_pda.signer.as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = vesting_pda_bump,
+        has_one = vesting_account,
+        owner = crate::id()
+    )]
+    pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
+
+    // Authority for the pooled escrow
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = presale_pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    pub operator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<RevokeGrant>, vesting_pda_bump: u8, presale_pda_bump: u8) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+    let user_vesting_pda = &mut ctx.accounts.user_vesting_pda;
+
+    // Only the part of revocable grants that hasn't vested yet is clawed back
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    let revocable_amount = user_vesting_pda.revocable_amount;
+    let vested = presale_account.vested_amount(revocable_amount, user_vesting_pda.lockup_multiplier_bps, current_timestamp)?;
+    let unvested = revocable_amount.saturating_sub(vested);
+    if unvested == 0 {
+        return Err(PresaleError::NothingToRevoke.into());
+    }
+
+    // Whatever has vested stays claimable, and can no longer be revoked or locked up again
+    // by the shrunk total the rest of the position keeps vesting on
+    user_vesting_pda.settle_penalty_rewards(presale_account)?;
+    user_vesting_pda.total_amount -= unvested;
+    user_vesting_pda.revocable_amount = 0;
+    user_vesting_pda.vested_floor = match user_vesting_pda.vested_floor.checked_add(vested) {
+        Some(val) => val,
+        None => return Err(PresaleError::NumericalOverflowError.into()),
+    };
+    user_vesting_pda.reset_penalty_debt(presale_account)?;
+    presale_account.fractions_vesting = presale_account.fractions_vesting.saturating_sub(unvested);
+
+    // Pooled fractions are held by the presale PDA rather than by the position
+    let position_key = user_vesting_pda.signer;
+    let presale_key = ctx.accounts.presale_account.key();
+    let vesting_seeds: &[&[u8]] = &[b"vesting".as_ref(), position_key.as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[vesting_pda_bump]];
+    let presale_seeds: &[&[u8]] = &[b"presale".as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[presale_pda_bump]];
+    let (vesting_authority, vesting_signer) = if ctx.accounts.presale_account.is_vesting_pooled() {
+        (ctx.accounts.presale_pda.to_account_info(), presale_seeds)
+    } else {
+        (ctx.accounts.user_vesting_pda.to_account_info(), vesting_seeds)
+    };
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(), 
+            Transfer {
+                from: ctx.accounts.vesting_account.to_account_info(),
+                to: ctx.accounts.fraction_treasury.to_account_info(),
+                authority: vesting_authority
+            },
+            &[vesting_signer]
+        ), 
+        unvested
+    )?;
+
+    Ok(())
+}
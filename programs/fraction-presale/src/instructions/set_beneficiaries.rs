@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetBeneficiaries<'info> {
+
+    #[account(
+        mut,
+        has_one = treasurer,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub treasurer: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetBeneficiaries>, beneficiaries: Vec<Beneficiary>) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Timelocked presales change their beneficiaries through `queue_action`
+    if presale_account.timelock_delay > 0 {
+        return Err(PresaleError::TimelockActive.into());
+    }
+
+    Beneficiary::validate(&beneficiaries)?;
+    presale_account.beneficiaries = beneficiaries;
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::state::*;
+
+#[derive(Accounts)]
+#[instruction(fee_recipient_bump: u8)]
+pub struct SetFeeRecipient<'info> {
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = program_config.bump,
+        has_one = admin,
+    )]
+    pub program_config: Box<Account<'info, ProgramConfig>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FEE_RECIPIENT_SIZE,
+        seeds = [b"fee_recipient".as_ref(), payment_mint.key().as_ref()],
+        bump = fee_recipient_bump,
+    )]
+    pub fee_recipient: Box<Account<'info, FeeRecipient>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    #[account(constraint = token_account.mint == payment_mint.key())]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+}
+
+
+// Existing presales keep paying the token account recorded when they were created
+pub(crate) fn handler(ctx: Context<SetFeeRecipient>) -> ProgramResult {
+    let payment_mint = ctx.accounts.payment_mint.key();
+    let fee_recipient = &mut ctx.accounts.fee_recipient;
+    fee_recipient.payment_mint = payment_mint;
+    fee_recipient.token_account = ctx.accounts.token_account.key();
+    Ok(())
+}
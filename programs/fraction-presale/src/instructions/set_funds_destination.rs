@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetFundsDestination<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+// `Pubkey::default()` pays the authority again
+pub(crate) fn handler(ctx: Context<SetFundsDestination>, funds_destination: Pubkey) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Timelocked presales change the destination through `queue_action`
+    if presale_account.timelock_delay > 0 {
+        return Err(PresaleError::TimelockActive.into());
+    }
+
+    presale_account.funds_destination = funds_destination;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetLockupOptions<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetLockupOptions>, lockup_options: Vec<LockupOption>) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Buyers must know the lockups on offer before they can purchase
+    if presale_account.started {
+        return Err(PresaleError::PresaleAlreadyStarted.into());
+    }
+
+    // Lockups stretch the vesting period, which lots and milestones don't have,
+    // and an early exit would let buyers keep the bonus without staying locked up
+    if presale_account.lot_duration > 0
+        || !presale_account.milestones.is_empty()
+        || presale_account.early_exit_penalty_bps > 0
+    {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    // A multiplier has nothing to stretch unless vesting runs past the end of the sale.
+    // Deferred presales get their vesting end from `set_vesting_start` instead
+    if !lockup_options.is_empty()
+        && !presale_account.is_vesting_deferred()
+        && presale_account.vesting_end <= presale_account.presale_end
+    {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    // An empty list withdraws the offer. Otherwise lockups can only be extended,
+    // and are listed from the shortest so each buyer's choice is identified by its multiplier
+    if lockup_options.len() > MAX_LOCKUP_OPTIONS {
+        return Err(PresaleError::InvalidLockupOptions.into());
+    }
+    let mut previous_multiplier_bps: u64 = 0;
+    for lockup_option in lockup_options.iter() {
+        let multiplier_bps = lockup_option.multiplier_bps as u64;
+        if multiplier_bps < BASIS_POINTS
+            || multiplier_bps <= previous_multiplier_bps
+            || lockup_option.bonus_bps as u64 > BASIS_POINTS
+        {
+            return Err(PresaleError::InvalidLockupOptions.into());
+        }
+        previous_multiplier_bps = multiplier_bps;
+    }
+
+    presale_account.lockup_options = lockup_options;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetMilestones<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetMilestones>, milestones: Vec<Milestone>) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Buyers must know the milestones before they can purchase
+    if presale_account.started {
+        return Err(PresaleError::PresaleAlreadyStarted.into());
+    }
+
+    // Milestones replace every other vesting schedule, and have no end to price an early exit against
+    if presale_account.lot_duration > 0
+        || presale_account.tge_unlock_bps > 0
+        || presale_account.is_vesting_deferred()
+        || presale_account.early_exit_penalty_bps > 0
+        || !presale_account.lockup_options.is_empty()
+    {
+        return Err(PresaleError::InvalidVestingSchedule.into());
+    }
+
+    // Every milestone releases something and together they release whole positions
+    if milestones.is_empty() || milestones.len() > MAX_MILESTONES {
+        return Err(PresaleError::InvalidMilestones.into());
+    }
+    // Deadlines fall after the sale, in the order the milestones are listed
+    let mut total_bps: u64 = 0;
+    let mut last_deadline = presale_account.presale_end;
+    for milestone in milestones.iter() {
+        if milestone.unlock_bps == 0 {
+            return Err(PresaleError::InvalidMilestones.into());
+        }
+        if milestone.deadline <= presale_account.presale_end || milestone.deadline < last_deadline {
+            return Err(PresaleError::InvalidMilestones.into());
+        }
+        last_deadline = milestone.deadline;
+        total_bps += milestone.unlock_bps as u64;
+    }
+    if total_bps != BASIS_POINTS {
+        return Err(PresaleError::InvalidMilestones.into());
+    }
+
+    presale_account.milestones = milestones.iter()
+        .map(|milestone| Milestone { reached: false, ..*milestone })
+        .collect();
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct SetMultisigOwners<'info> {
+
+    // Only signs through `execute_transaction`, so the owners have to approve their own replacement
+    #[account(
+        mut,
+        signer,
+        owner = crate::id()
+    )]
+    pub multisig: Box<Account<'info, Multisig>>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetMultisigOwners>, owners: Vec<Pubkey>, threshold: u8) -> ProgramResult {
+    Multisig::validate_owners(&owners, threshold)?;
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.owner_set_seqno = multisig.owner_set_seqno.wrapping_add(1);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetRoles<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetRoles>, operator: Pubkey, treasurer: Pubkey, pauser: Pubkey) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // The treasurer decides where funds go by default, so timelocked presales change roles through `queue_action`
+    if presale_account.timelock_delay > 0 {
+        return Err(PresaleError::TimelockActive.into());
+    }
+
+    presale_account.set_roles(operator, treasurer, pauser);
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetTimelockDelay<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetTimelockDelay>, timelock_delay: u64) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    if timelock_delay > MAX_TIMELOCK_DELAY {
+        return Err(PresaleError::InvalidTimelockDelay.into());
+    }
+
+    // Buyers are only ever given more notice directly, shortening the delay has to wait it out
+    if timelock_delay < presale_account.timelock_delay {
+        return Err(PresaleError::TimelockActive.into());
+    }
+
+    presale_account.timelock_delay = timelock_delay;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetTransferPolicy<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetTransferPolicy>, requires_authority: bool, vesting_receipts: bool) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Buyers must know whether they can freely move their positions before they purchase
+    if presale_account.started {
+        return Err(PresaleError::PresaleAlreadyStarted.into());
+    }
+
+    // Receipts trade on ordinary token rails, out of the authority's reach
+    if requires_authority && vesting_receipts {
+        return Err(PresaleError::InvalidTransferPolicy.into());
+    }
+
+    presale_account.transfer_requires_authority = requires_authority;
+    presale_account.vesting_receipts = vesting_receipts;
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(vesting_pda_bump: u8)]
+pub struct SetUnlockDelegate<'info> {
+
+    #[account(owner = crate::id())]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    #[account(
+        mut,
+        seeds = [b"vesting".as_ref(), user_vesting_pda.signer.as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = vesting_pda_bump,
+        constraint = user_vesting_pda.owner == owner.key(),
+        owner = crate::id()
+    )]
+    pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
+
+    pub owner: Signer<'info>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetUnlockDelegate>, delegate: Option<Pubkey>) -> ProgramResult {
+    // Whoever holds the receipt of a tokenized position changes without the program knowing,
+    // so there is no owner to pay a delegate's unlocks to
+    if ctx.accounts.user_vesting_pda.is_tokenized() {
+        return Err(PresaleError::PositionIsTokenized.into());
+    }
+
+    ctx.accounts.user_vesting_pda.delegate = delegate.unwrap_or_default();
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::clock::Clock;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetVestingStart<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(ctx: Context<SetVestingStart>, vesting_start: u64) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    if !presale_account.is_vesting_deferred() {
+        return Err(PresaleError::VestingNotDeferred.into());
+    }
+
+    // The vesting start can only ever be set once
+    if presale_account.vesting_start > 0 {
+        return Err(PresaleError::VestingStartAlreadySet.into());
+    }
+
+    // It is set after the sale closes, and never once the fallback start has been reached
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    if current_timestamp < presale_account.presale_end || current_timestamp > presale_account.vesting_start_deadline {
+        return Err(PresaleError::InvalidVestingStart.into());
+    }
+    if vesting_start < presale_account.presale_end || vesting_start > presale_account.vesting_start_deadline {
+        return Err(PresaleError::InvalidVestingStart.into());
+    }
+
+    presale_account.vesting_start = vesting_start;
+    presale_account.tge_timestamp = vesting_start;
+    presale_account.vesting_end = match vesting_start.checked_add(presale_account.vesting_duration) {
+        Some(val) => val,
+        None => return Err(PresaleError::NumericalOverflowError.into()),
+    };
+    Ok(())
+}
@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
 use anchor_lang::solana_program::sysvar::clock::Clock;
 
 use crate::state::*;
@@ -10,17 +9,17 @@ pub struct StartPresale<'info> {
 
     #[account(
         mut,
-        has_one = authority,
+        has_one = operator,
         owner = crate::id()
     )]
     pub presale_account: Account<'info, PresaleInfo>,
 
-    pub authority: Signer<'info>
+    pub operator: Signer<'info>
 
 }
 
 
-pub fn handler(ctx: Context<StartPresale>) -> ProgramResult {
+pub(crate) fn handler(ctx: Context<StartPresale>) -> ProgramResult {
     let presale_account = &mut ctx.accounts.presale_account;
 
     // Make sure presale is never started more than once
@@ -28,7 +27,13 @@ pub fn handler(ctx: Context<StartPresale>) -> ProgramResult {
         return Err(PresaleError::PresaleAlreadyStarted.into());
     }
 
+    // Finalizing a presale that never started has already released its fractions
+    if presale_account.finalized {
+        return Err(PresaleError::PresaleAlreadyFinalized.into());
+    }
+
     presale_account.presale_start = Clock::get()?.unix_timestamp as u64;
     presale_account.started = true;
+    presale_account.start_price = presale_account.price;
     Ok(())
 }
\ No newline at end of file
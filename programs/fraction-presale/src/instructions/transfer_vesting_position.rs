@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(vesting_pda_bump: u8)]
+pub struct TransferVestingPosition<'info> {
+
+    #[account(
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Box<Account<'info, PresaleInfo>>,
+
+    // The PDA keeps the seeds of the buyer who opened it, only its owner changes
+    #[account(
+        mut,
+        seeds = [b"vesting".as_ref(), user_vesting_pda.signer.as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = vesting_pda_bump,
+        constraint = user_vesting_pda.owner == owner.key(),
+        owner = crate::id()
+    )]
+    pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
+
+    pub owner: Signer<'info>,
+
+    pub new_owner: AccountInfo<'info>,
+
+    // Only needs to sign when the presale requires it to approve transfers
+    pub authority: AccountInfo<'info>,
+
+}
+
+
+pub(crate) fn handler(ctx: Context<TransferVestingPosition>) -> ProgramResult {
+    if ctx.accounts.user_vesting_pda.is_tokenized() {
+        return Err(PresaleError::PositionIsTokenized.into());
+    }
+    if ctx.accounts.presale_account.transfer_requires_authority && !ctx.accounts.authority.is_signer {
+        return Err(PresaleError::TransferNotApproved.into());
+    }
+
+    // Balances, claims, lots and credited penalties all stay with the position, the previous owner's delegate does not
+    ctx.accounts.user_vesting_pda.owner = ctx.accounts.new_owner.key();
+    ctx.accounts.user_vesting_pda.delegate = Pubkey::default();
+    Ok(())
+}
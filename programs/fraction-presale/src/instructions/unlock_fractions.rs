@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, Token, Mint, TokenAccount};
+use anchor_spl::token::{self, Burn, Transfer, Token, TokenAccount};
 
 use crate::state::*;
-use crate::errors::*;
+use crate::errors::PresaleError;
 
 #[derive(Accounts)]
-#[instruction(vesting_pda_bump: u8)]
+#[instruction(vesting_pda_bump: u8, presale_pda_bump: u8)]
 pub struct UnlockFractions<'info> {
 
     #[account(
@@ -22,29 +22,38 @@ pub struct UnlockFractions<'info> {
     #[account(mut)]
     pub payment_treasury: Box<Account<'info, TokenAccount>>,
 
+    // Any fraction account, so unlocks can go straight to a custodian or an exchange deposit address.
+    // Delegates can only unlock into accounts owned by the position's owner
     #[account(
         mut,
         constraint = to_account.mint == fraction_treasury.mint,
-        constraint = to_account.owner == signer.key(),
     )]
     pub to_account: Box<Account<'info, TokenAccount>>,
 
     #[account(
         mut,
         constraint = vesting_account.mint == fraction_treasury.mint,
-        constraint = vesting_account.owner == user_vesting_pda.key(),
+        constraint = vesting_account.owner == user_vesting_pda.key() || vesting_account.key() == presale_account.vesting_escrow,
     )]
     pub vesting_account: Box<Account<'info, TokenAccount>>,
 
     #[account(
-        seeds = [b"vesting".as_ref(), signer.key().as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        mut,
+        seeds = [b"vesting".as_ref(), user_vesting_pda.signer.as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
         bump = vesting_pda_bump,
         has_one = vesting_account,
-        has_one = signer,
         owner = crate::id()
     )]
     pub user_vesting_pda: Box<Account<'info, VestingInfo>>,
 
+    // Authority for `fraction_treasury`, which pays out redistributed penalties, and for the pooled escrow
+    #[account(
+        seeds = [b"presale".as_ref(), presale_account.key().as_ref(), crate::id().as_ref()],
+        bump = presale_pda_bump
+    )]
+    pub presale_pda: AccountInfo<'info>,
+
+    // The position's owner or its delegate, or the holder of its receipt when it is tokenized
     pub signer: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
@@ -52,20 +61,67 @@ pub struct UnlockFractions<'info> {
 }
 
 
-pub fn handler(ctx: Context<UnlockFractions>, vesting_pda_bump: u8) -> ProgramResult {
+pub(crate) fn handler<'info>(ctx: Context<'_, '_, '_, 'info, UnlockFractions<'info>>, vesting_pda_bump: u8, presale_pda_bump: u8, amount: Option<u64>) -> ProgramResult {
+
+    // A delegate claims on the owner's behalf, so the fractions must still go to the owner.
+    // Tokenized positions have no owner to pay, only the receipt holder can unlock them
+    let user_vesting_pda = &ctx.accounts.user_vesting_pda;
+    let receipt = if !user_vesting_pda.is_tokenized() && user_vesting_pda.is_delegate(ctx.accounts.signer.key) {
+        if ctx.accounts.to_account.owner != user_vesting_pda.owner {
+            return Err(PresaleError::DelegateMustPayOwner.into());
+        }
+        None
+    } else {
+        user_vesting_pda.authorize(ctx.remaining_accounts, ctx.accounts.signer.key)?
+    };
 
     let presale_account = &mut ctx.accounts.presale_account;
     let vesting_account = &ctx.accounts.vesting_account;
 
-    // Make sure there are enough tokens to transfer
-    if vesting_account.amount == 0 {
+    // Make sure there are enough tokens to transfer, which a pooled escrow can't tell for a single position
+    if ctx.accounts.user_vesting_pda.locked_amount() == 0 {
         return Err(PresaleError::VestingAccountIsEmpty.into());
     }
     
     let current_timestamp = Clock::get()?.unix_timestamp as u64;
-    if current_timestamp < presale_account.vesting_end {
-        return Err(PresaleError::VestingPeriodNotFinished.into());
-    }
+    let amount_to_unlock = if presale_account.lot_duration > 0 {
+        // Only the lots whose own vesting period has passed are released
+        let mut vesting_lots = VestingLots::load(ctx.remaining_accounts, &ctx.accounts.user_vesting_pda.key())?;
+        let amount_to_unlock = requested_amount(amount, vesting_lots.matured_amount(current_timestamp)?)?;
+        vesting_lots.release(current_timestamp, amount_to_unlock);
+        vesting_lots.exit(ctx.program_id)?;
+        amount_to_unlock
+    } else {
+        // The TGE share can be claimed first, the remainder once the buyer's lockup is over
+        let user_vesting_pda = &ctx.accounts.user_vesting_pda;
+        let vested = user_vesting_pda.vested_amount(presale_account, current_timestamp)?;
+        requested_amount(amount, vested.saturating_sub(user_vesting_pda.claimed_amount))?
+    };
+
+    // Penalties redistributed so far are paid out in full along with the unlocked fractions, on top of
+    // `amount` rather than out of it, so even a partial unlock leaves no penalty rewards behind
+    let user_vesting_pda = &mut ctx.accounts.user_vesting_pda;
+    user_vesting_pda.settle_penalty_rewards(presale_account)?;
+    let penalty_rewards = user_vesting_pda.penalty_rewards;
+    user_vesting_pda.penalty_rewards = 0;
+    user_vesting_pda.claimed_amount = match user_vesting_pda.claimed_amount.checked_add(amount_to_unlock) {
+        Some(val) => val,
+        None => return Err(PresaleError::NumericalOverflowError.into()),
+    };
+    user_vesting_pda.reset_penalty_debt(presale_account)?;
+
+    presale_account.release_vesting(amount_to_unlock, penalty_rewards);
+
+    // Pooled fractions are held by the presale PDA rather than by the position
+    let position_key = ctx.accounts.user_vesting_pda.signer;
+    let presale_key = ctx.accounts.presale_account.key();
+    let vesting_seeds: &[&[u8]] = &[b"vesting".as_ref(), position_key.as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[vesting_pda_bump]];
+    let presale_seeds: &[&[u8]] = &[b"presale".as_ref(), presale_key.as_ref(), ctx.program_id.as_ref(), &[presale_pda_bump]];
+    let (vesting_authority, vesting_signer) = if ctx.accounts.presale_account.is_vesting_pooled() {
+        (ctx.accounts.presale_pda.to_account_info(), presale_seeds)
+    } else {
+        (ctx.accounts.user_vesting_pda.to_account_info(), vesting_seeds)
+    };
 
     token::transfer(
         CpiContext::new_with_signer(
@@ -73,12 +129,57 @@ pub fn handler(ctx: Context<UnlockFractions>, vesting_pda_bump: u8) -> ProgramRe
             Transfer {
                 from: vesting_account.to_account_info(),
                 to: ctx.accounts.to_account.to_account_info(),
-                authority: ctx.accounts.user_vesting_pda.to_account_info()
+                authority: vesting_authority
             },
-            &[&[b"vesting".as_ref(), ctx.accounts.signer.key().as_ref(), ctx.accounts.presale_account.key().as_ref(), ctx.program_id.as_ref(), &[vesting_pda_bump]]]
+            &[vesting_signer]
         ), 
-        vesting_account.amount
+        amount_to_unlock
     )?;
 
+    if penalty_rewards > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(), 
+                Transfer {
+                    from: ctx.accounts.fraction_treasury.to_account_info(),
+                    to: ctx.accounts.to_account.to_account_info(),
+                    authority: ctx.accounts.presale_pda.to_account_info()
+                },
+                &[presale_seeds]
+            ), 
+            penalty_rewards
+        )?;
+    }
+
+    // The receipt goes once nothing is left to claim, and the position takes in no more fractions after that
+    if let Some((receipt_mint, receipt_account)) = receipt {
+        if ctx.accounts.user_vesting_pda.locked_amount() == 0 {
+            token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(), 
+                    Burn {
+                        mint: receipt_mint,
+                        to: receipt_account.to_account_info(),
+                        authority: ctx.accounts.signer.to_account_info()
+                    }
+                ), 
+                1
+            )?;
+        }
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
+
+// Everything unlockable is released unless the caller asks for less
+fn requested_amount(amount: Option<u64>, unlockable: u64) -> Result<u64, ProgramError> {
+    if unlockable == 0 {
+        return Err(PresaleError::VestingPeriodNotFinished.into());
+    }
+    match amount {
+        None => Ok(unlockable),
+        Some(0) => Err(PresaleError::AmountIsZero.into()),
+        Some(val) if val > unlockable => Err(PresaleError::AmountExceedsUnlockable.into()),
+        Some(val) => Ok(val),
+    }
+}
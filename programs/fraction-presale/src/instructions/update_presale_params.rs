@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::clock::Clock;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdatePresaleParams<'info> {
+
+    #[account(
+        mut,
+        has_one = authority,
+        owner = crate::id()
+    )]
+    pub presale_account: Account<'info, PresaleInfo>,
+
+    pub authority: Signer<'info>
+
+}
+
+
+pub(crate) fn handler(
+    ctx: Context<UpdatePresaleParams>,
+    price: Option<u64>,
+    max_amount: Option<u64>,
+    presale_end: Option<u64>,
+    vesting_end: Option<u64>
+) -> ProgramResult {
+    let presale_account = &mut ctx.accounts.presale_account;
+
+    // Timelocked presales change their parameters through `queue_action`
+    if presale_account.timelock_delay > 0 {
+        return Err(PresaleError::TimelockActive.into());
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp as u64;
+    presale_account.update_params(price, max_amount, presale_end, vesting_end, current_timestamp)?;
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = program_config.bump,
+        has_one = admin,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub admin: Signer<'info>
+
+}
+
+
+// Presales keep the fee they were created with
+pub(crate) fn handler(ctx: Context<UpdateProgramConfig>, admin: Pubkey, fee_bps: u16) -> ProgramResult {
+    if fee_bps as u64 > MAX_FEE_BPS {
+        return Err(PresaleError::InvalidFee.into());
+    }
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.admin = admin;
+    program_config.fee_bps = fee_bps;
+    Ok(())
+}
@@ -5,6 +5,7 @@ pub mod state;
 pub mod instructions;
 
 use instructions::*;
+use state::{Milestone, LockupOption, AirdropAllocation, TransactionAccount, AdminAction, Beneficiary};
 
 declare_id!("EmcETFRC5ftDYwNn6cHB3zQioNH1z8cRSwx5MZC1BMBU");
 
@@ -13,20 +14,52 @@ pub mod fraction_presale {
 
     use super::*;
 
+    // Create the counter that numbers every presale, which anyone can do once
+    pub fn init_registry(ctx: Context<InitRegistry>, registry_bump: u8) -> ProgramResult {
+        instructions::init_registry::handler(ctx, registry_bump)
+    }
+
     // Setup the presale account
-    pub fn initialize_presale(
-        ctx: Context<InitializePresale>, 
-        pda_bump: u8,
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_presale<'info>(
+        ctx: Context<'_, '_, '_, 'info, InitializePresale<'info>>, 
+        _pda_bump: u8,
+        _presale_bump: u8,
+        _presale_index_bump: u8,
+        presale_index_page: u32,
         price: u64,
         max_amount: u64,
         presale_end: u64,
-        vesting_end: u64
+        vesting_end: u64,
+        lot_duration: u64,
+        tge_timestamp: u64,
+        tge_unlock_bps: u16,
+        vesting_start_deadline: u64,
+        vesting_duration: u64
     ) -> ProgramResult {
-        instructions::initialize_presale::handler(ctx, price, max_amount, presale_end, vesting_end)
+        instructions::initialize_presale::handler(
+            ctx, presale_index_page, price, max_amount, presale_end, vesting_end, lot_duration,
+            tge_timestamp, tge_unlock_bps, vesting_start_deadline, vesting_duration
+        )
+    }
+
+    // Create the platform config, which only the program's upgrade authority can do
+    pub fn init_program_config(ctx: Context<InitProgramConfig>, config_bump: u8, admin: Pubkey, fee_bps: u16) -> ProgramResult {
+        instructions::init_program_config::handler(ctx, config_bump, admin, fee_bps)
+    }
+
+    // Change the platform admin and the fee for presales created from now on
+    pub fn update_program_config(ctx: Context<UpdateProgramConfig>, admin: Pubkey, fee_bps: u16) -> ProgramResult {
+        instructions::update_program_config::handler(ctx, admin, fee_bps)
+    }
+
+    // Set the token account receiving the platform fee of presales paid in a payment mint
+    pub fn set_fee_recipient(ctx: Context<SetFeeRecipient>, _fee_recipient_bump: u8) -> ProgramResult {
+        instructions::set_fee_recipient::handler(ctx)
     }
 
     // Add fraction to sell in the presale
-    pub fn add_fractions_for_sale(ctx: Context<AddFractions>, pda_bump: u8, amount: u64) -> ProgramResult {
+    pub fn add_fractions_for_sale(ctx: Context<AddFractions>, _pda_bump: u8, amount: u64) -> ProgramResult {
         instructions::add_fractions::handler(ctx, amount)
     }
 
@@ -39,23 +72,201 @@ pub mod fraction_presale {
         instructions::start_presale::handler(ctx)
     }
 
+    // Correct the sale parameters, which are restricted to changes in buyers' favour once started
+    pub fn update_presale_params(
+        ctx: Context<UpdatePresaleParams>,
+        price: Option<u64>,
+        max_amount: Option<u64>,
+        presale_end: Option<u64>,
+        vesting_end: Option<u64>
+    ) -> ProgramResult {
+        instructions::update_presale_params::handler(ctx, price, max_amount, presale_end, vesting_end)
+    }
+
+    // Require admin changes to be queued for a delay, which can only be shortened through the queue itself
+    pub fn set_timelock_delay(ctx: Context<SetTimelockDelay>, timelock_delay: u64) -> ProgramResult {
+        instructions::set_timelock_delay::handler(ctx, timelock_delay)
+    }
+
+    // Pay collected funds out to another wallet than the authority's
+    pub fn set_funds_destination(ctx: Context<SetFundsDestination>, funds_destination: Pubkey) -> ProgramResult {
+        instructions::set_funds_destination::handler(ctx, funds_destination)
+    }
+
+    // Hand the operator, treasurer and pauser roles to other keys
+    pub fn set_roles(ctx: Context<SetRoles>, operator: Pubkey, treasurer: Pubkey, pauser: Pubkey) -> ProgramResult {
+        instructions::set_roles::handler(ctx, operator, treasurer, pauser)
+    }
+
+    // Split raised funds between token accounts by basis point shares, or pass an empty list to collect them whole again
+    pub fn set_beneficiaries(ctx: Context<SetBeneficiaries>, beneficiaries: Vec<Beneficiary>) -> ProgramResult {
+        instructions::set_beneficiaries::handler(ctx, beneficiaries)
+    }
+
+    // Halt purchases
+    pub fn pause_presale(ctx: Context<PausePresale>) -> ProgramResult {
+        instructions::pause_presale::handler(ctx)
+    }
+
+    pub fn resume_presale(ctx: Context<ResumePresale>) -> ProgramResult {
+        instructions::resume_presale::handler(ctx)
+    }
+
+    // Announce an admin change that can be executed once the timelock delay has passed
+    pub fn queue_action(ctx: Context<QueueAction>, _action_bump: u8, action: AdminAction) -> ProgramResult {
+        instructions::queue_action::handler(ctx, action)
+    }
+
+    pub fn cancel_action(ctx: Context<CancelAction>) -> ProgramResult {
+        instructions::cancel_action::handler(ctx)
+    }
+
+    pub fn execute_action<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteAction<'info>>, pda_bump: u8) -> ProgramResult {
+        instructions::execute_action::handler(ctx, pda_bump)
+    }
+
+    // Nominate a new authority, which takes over once it accepts
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> ProgramResult {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    // Take over as authority of the presale after being proposed
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> ProgramResult {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    // Hand the presale over to an M-of-N multisig of the given owners
+    pub fn create_multisig(ctx: Context<CreateMultisig>, _multisig_bump: u8, owners: Vec<Pubkey>, threshold: u8) -> ProgramResult {
+        instructions::create_multisig::handler(ctx, owners, threshold)
+    }
+
+    // Replace the multisig owners, only callable by the multisig itself
+    pub fn set_multisig_owners(ctx: Context<SetMultisigOwners>, owners: Vec<Pubkey>, threshold: u8) -> ProgramResult {
+        instructions::set_multisig_owners::handler(ctx, owners, threshold)
+    }
+
+    // Propose an instruction for the multisig to sign, approving it as the proposer
+    pub fn propose_transaction(ctx: Context<ProposeTransaction>, target_program_id: Pubkey, transaction_accounts: Vec<TransactionAccount>, data: Vec<u8>) -> ProgramResult {
+        instructions::propose_transaction::handler(ctx, target_program_id, transaction_accounts, data)
+    }
+
+    pub fn approve_transaction(ctx: Context<ApproveTransaction>) -> ProgramResult {
+        instructions::approve_transaction::handler(ctx)
+    }
+
+    // Execute a proposed instruction with the multisig's signature once enough owners have approved it
+    pub fn execute_transaction(ctx: Context<ExecuteTransaction>, multisig_bump: u8) -> ProgramResult {
+        instructions::execute_transaction::handler(ctx, multisig_bump)
+    }
+
+    // Replace the vesting timestamps with delivery milestones
+    pub fn set_milestones(ctx: Context<SetMilestones>, milestones: Vec<Milestone>) -> ProgramResult {
+        instructions::set_milestones::handler(ctx, milestones)
+    }
+
+    // Attest that a milestone has been delivered
+    pub fn mark_milestone_reached(ctx: Context<MarkMilestoneReached>, index: u8) -> ProgramResult {
+        instructions::mark_milestone_reached::handler(ctx, index)
+    }
+
+    // Allow buyers to leave vesting early for a penalty
+    pub fn configure_early_exit(ctx: Context<ConfigureEarlyExit>, penalty_bps: u16, penalty_to_pool: bool) -> ProgramResult {
+        instructions::configure_early_exit::handler(ctx, penalty_bps, penalty_to_pool)
+    }
+
+    // Offer longer lockups that buyers can opt into for bonus fractions
+    pub fn set_lockup_options(ctx: Context<SetLockupOptions>, lockup_options: Vec<LockupOption>) -> ProgramResult {
+        instructions::set_lockup_options::handler(ctx, lockup_options)
+    }
+
+    // Require the authority to co-sign vesting position transfers, or tokenize positions as receipts
+    pub fn set_transfer_policy(ctx: Context<SetTransferPolicy>, requires_authority: bool, vesting_receipts: bool) -> ProgramResult {
+        instructions::set_transfer_policy::handler(ctx, requires_authority, vesting_receipts)
+    }
+
+    // Fix the vesting start of a deferred presale once the sale has closed
+    pub fn set_vesting_start(ctx: Context<SetVestingStart>, vesting_start: u64) -> ProgramResult {
+        instructions::set_vesting_start::handler(ctx, vesting_start)
+    }
+
+    // Return or burn the fractions left unsold once the presale has ended, which anyone can trigger
+    pub fn finalize_presale<'info>(ctx: Context<'_, '_, '_, 'info, FinalizePresale<'info>>, pda_bump: u8) -> ProgramResult {
+        instructions::finalize_presale::handler(ctx, pda_bump)
+    }
+
+    // Close a finalized presale once every position is closed and its funds collected, returning all rent to the authority
+    pub fn close_presale<'info>(ctx: Context<'_, '_, '_, 'info, ClosePresale<'info>>, pda_bump: u8) -> ProgramResult {
+        instructions::close_presale::handler(ctx, pda_bump)
+    }
+
     // Withdraw funds used to purchase fractions
-    pub fn collect_funds(ctx: Context<CollectFunds>, pda_bump: u8) -> ProgramResult {
+    pub fn collect_funds<'info>(ctx: Context<'_, '_, '_, 'info, CollectFunds<'info>>, pda_bump: u8) -> ProgramResult {
         instructions::collect_funds::handler(ctx, pda_bump)
     }
 
-    pub fn init_vesting_account(ctx: Context<InitVesting>, vesting_pda_bump: u8) -> ProgramResult {
+    // Pay the raised funds out to the beneficiaries, which anyone can trigger
+    pub fn distribute_funds<'info>(ctx: Context<'_, '_, '_, 'info, DistributeFunds<'info>>, pda_bump: u8) -> ProgramResult {
+        instructions::distribute_funds::handler(ctx, pda_bump)
+    }
+
+    // Pool every position's fractions in a single escrow instead of a token account per buyer
+    pub fn init_vesting_escrow(ctx: Context<InitVestingEscrow>, _presale_pda_bump: u8, _escrow_bump: u8) -> ProgramResult {
+        instructions::init_vesting_escrow::handler(ctx)
+    }
+
+    pub fn init_vesting_account(ctx: Context<InitVesting>, _vesting_pda_bump: u8) -> ProgramResult {
         instructions::init_vesting_account::handler(ctx)
     }
 
-    // Buy fractions with redeem tokens
-    pub fn purchase_fractions(ctx: Context<PurchaseFractions>, presale_pda_bump: u8, vesting_pda_bump: u8, amount: u64) -> ProgramResult {
-        instructions::purchase_fractions::handler(ctx, presale_pda_bump, amount)
+    // Open the account that records each purchase as its own vesting lot
+    pub fn init_vesting_lots(ctx: Context<InitVestingLots>, _vesting_pda_bump: u8, _lots_pda_bump: u8) -> ProgramResult {
+        instructions::init_vesting_lots::handler(ctx)
+    }
+
+    // Buy fractions with redeem tokens, opening the vesting account on the first purchase if needed
+    pub fn purchase_fractions<'info>(ctx: Context<'_, '_, '_, 'info, PurchaseFractions<'info>>, presale_pda_bump: u8, vesting_pda_bump: u8, amount: u64, lockup_option: u8) -> ProgramResult {
+        instructions::purchase_fractions::handler(ctx, presale_pda_bump, vesting_pda_bump, amount, lockup_option)
+    }
+
+    // Unlock fractions that have vested, at TGE and after the vesting period, all of them unless an amount is given.
+    // Accrued penalty rewards are always paid out in full on top of the amount
+    pub fn unlock_fractions<'info>(ctx: Context<'_, '_, '_, 'info, UnlockFractions<'info>>, vesting_pda_bump: u8, presale_pda_bump: u8, amount: Option<u64>) -> ProgramResult {
+        instructions::unlock_fractions::handler(ctx, vesting_pda_bump, presale_pda_bump, amount)
+    }
+
+    // Leave vesting before it ends, giving up a penalty on the fractions that have not vested yet
+    pub fn exit_vesting_early<'info>(ctx: Context<'_, '_, '_, 'info, ExitVestingEarly<'info>>, vesting_pda_bump: u8, presale_pda_bump: u8) -> ProgramResult {
+        instructions::exit_vesting_early::handler(ctx, vesting_pda_bump, presale_pda_bump)
+    }
+
+    // Allocate fractions into a vesting position without payment, optionally revocable while unvested
+    pub fn grant_fractions<'info>(ctx: Context<'_, '_, '_, 'info, GrantFractions<'info>>, presale_pda_bump: u8, _vesting_pda_bump: u8, amount: u64, revocable: bool) -> ProgramResult {
+        instructions::grant_fractions::handler(ctx, presale_pda_bump, amount, revocable)
+    }
+
+    // Return the unvested part of a position's revocable grants to the treasury
+    pub fn revoke_grant(ctx: Context<RevokeGrant>, vesting_pda_bump: u8, presale_pda_bump: u8) -> ProgramResult {
+        instructions::revoke_grant::handler(ctx, vesting_pda_bump, presale_pda_bump)
+    }
+
+    // Allocate fractions to many recipients' vesting positions at once, opening the missing ones
+    pub fn airdrop_fractions<'info>(ctx: Context<'_, '_, '_, 'info, AirdropFractions<'info>>, presale_pda_bump: u8, allocations: Vec<AirdropAllocation>) -> ProgramResult {
+        instructions::airdrop_fractions::handler(ctx, presale_pda_bump, allocations)
+    }
+
+    // Hand a whole vesting position over to another wallet
+    pub fn transfer_vesting_position(ctx: Context<TransferVestingPosition>, _vesting_pda_bump: u8) -> ProgramResult {
+        instructions::transfer_vesting_position::handler(ctx)
+    }
+
+    // Let another key unlock fractions on the owner's behalf, or revoke it with `None`
+    pub fn set_unlock_delegate(ctx: Context<SetUnlockDelegate>, _vesting_pda_bump: u8, delegate: Option<Pubkey>) -> ProgramResult {
+        instructions::set_unlock_delegate::handler(ctx, delegate)
     }
 
-    // Unlock fractions after vesting period
-    pub fn unlock_fractions(ctx: Context<UnlockFractions>, vesting_pda_bump: u8) -> ProgramResult {
-        instructions::unlock_fractions::handler(ctx, vesting_pda_bump)
+    // Close a fully claimed vesting position and return its rent
+    pub fn close_vesting_account<'info>(ctx: Context<'_, '_, '_, 'info, CloseVestingAccount<'info>>, vesting_pda_bump: u8) -> ProgramResult {
+        instructions::close_vesting_account::handler(ctx, vesting_pda_bump)
     }
 
 }
@@ -1,15 +1,217 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::{invoke, invoke_signed}, system_instruction};
+use anchor_spl::token::TokenAccount;
 
-pub const VESTING_INFO_SIZE: usize = 32 + 32;
-pub const PRESALE_INFO_SIZE: usize = 32 + 32 + 32 + 32 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 8 + 8;
+use crate::errors::PresaleError;
+
+pub const VESTING_INFO_SIZE: usize = 32 + 32 + 8 + 8 + 16 + 8 + 2 + 32 + 32 + 32 + 8 + 8;
+pub const PRESALE_INFO_SIZE: usize = 32 + 32 + 32 + 32 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 2 + 8 + 8 + 8 + 4 + MAX_MILESTONES * MILESTONE_SIZE
+    + 2 + 1 + 8 + 16 + 8 + 4 + MAX_LOCKUP_OPTIONS * LOCKUP_OPTION_SIZE + 1 + 1 + 32 + 32 + 8
+    + 8 + 32 + 8
+    + 32 + 32 + 32 + 1 + 4 + MAX_BENEFICIARIES * BENEFICIARY_SIZE + 2 + 32
+    + 32 + 1 + 8
+    + 8;
+
+pub const BASIS_POINTS: u64 = 10_000;
+pub const MAX_PRICE_CHANGE_BPS: u64 = 1_000;
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60;
+pub const MAX_FEE_BPS: u64 = 1_000;
+pub const PENALTY_PRECISION: u128 = 1_000_000_000_000;
+
+pub const MAX_MILESTONES: usize = 8;
+pub const MILESTONE_SIZE: usize = 2 + 8 + 1;
+
+pub const MAX_BENEFICIARIES: usize = 8;
+pub const BENEFICIARY_SIZE: usize = 32 + 2;
+
+pub const MAX_LOCKUP_OPTIONS: usize = 4;
+pub const LOCKUP_OPTION_SIZE: usize = 2 + 2;
+
+// Matured lots are merged to make room, so this only limits the lots still vesting at the same time
+pub const MAX_VESTING_LOTS: usize = 16;
+pub const VESTING_LOT_SIZE: usize = 8 + 8 + 8;
+pub const VESTING_LOTS_SIZE: usize = 32 + 4 + MAX_VESTING_LOTS * VESTING_LOT_SIZE;
+
+pub const PROGRAM_CONFIG_SIZE: usize = 32 + 2 + 1;
+pub const FEE_RECIPIENT_SIZE: usize = 32 + 32;
+
+pub const REGISTRY_SIZE: usize = 8 + 1;
+pub const MAX_INDEXED_PRESALES: usize = 32;
+pub const PRESALE_INDEX_SIZE: usize = 32 + 4 + 4 + MAX_INDEXED_PRESALES * 32;
+
+pub const QUEUED_ACTION_SIZE: usize = 32 + 1 + 4 + MAX_BENEFICIARIES * BENEFICIARY_SIZE + 8 + 32;
+
+pub const MAX_MULTISIG_OWNERS: usize = 10;
+pub const MULTISIG_SIZE: usize = 32 + 4 + MAX_MULTISIG_OWNERS * 32 + 1 + 4;
+
+pub const MAX_TRANSACTION_ACCOUNTS: usize = 16;
+pub const TRANSACTION_ACCOUNT_SIZE: usize = 32 + 1 + 1;
+pub const MAX_TRANSACTION_DATA: usize = 512;
+pub const MULTISIG_TRANSACTION_SIZE: usize = 32 + 32 + 4 + MAX_TRANSACTION_ACCOUNTS * TRANSACTION_ACCOUNT_SIZE
+    + 4 + MAX_TRANSACTION_DATA + 4 + MAX_MULTISIG_OWNERS + 1 + 4 + 32;
 
 #[account]
+#[derive(Default)]
 pub struct VestingInfo {
 
-    pub signer: Pubkey,
+    pub signer: Pubkey, // buyer who opened the position, which seeds this PDA even after it is transferred
 
     pub vesting_account: Pubkey,
 
+    pub total_amount: u64, // every fraction ever vested into this account
+
+    pub claimed_amount: u64, // fractions already unlocked out of `total_amount`
+
+    pub penalty_debt: u128, // share of `penalty_per_fraction` already credited to this account
+
+    pub penalty_rewards: u64, // early exit penalties credited to this account but not yet paid out
+
+    pub lockup_multiplier_bps: u16, // lockup chosen on the first purchase, zero when none was chosen yet
+
+    pub owner: Pubkey, // wallet currently entitled to the position, starting as `signer`
+
+    pub receipt_mint: Pubkey, // one-of-one token whose holder is entitled to the position instead of `owner`, when minted
+
+    pub delegate: Pubkey, // may unlock on the owner's behalf, into the owner's own accounts, when set
+
+    pub revocable_amount: u64, // granted fractions the authority can claw back for as long as they are unvested
+
+    pub vested_floor: u64, // fractions of revoked grants that had already vested, unlocked whatever the schedule says
+
+}
+
+impl VestingInfo {
+
+    // Starts an empty position owned by the buyer opening it
+    pub fn open(&mut self, signer: Pubkey, vesting_account: Pubkey) {
+        *self = VestingInfo {
+            signer,
+            vesting_account,
+            owner: signer,
+            ..VestingInfo::default()
+        };
+    }
+
+    pub fn locked_amount(&self) -> u64 {
+        self.total_amount.saturating_sub(self.claimed_amount)
+    }
+
+    // Fractions of `total_amount` vested by `timestamp`. Those kept from revoked grants vest right away
+    pub fn vested_amount(&self, presale: &PresaleInfo, timestamp: u64) -> Result<u64, ProgramError> {
+        let scheduled = presale.vested_amount(self.total_amount.saturating_sub(self.vested_floor), self.lockup_multiplier_bps, timestamp)?;
+        match scheduled.checked_add(self.vested_floor) {
+            Some(val) => Ok(val),
+            None => Err(PresaleError::NumericalOverflowError.into()),
+        }
+    }
+
+    pub fn is_tokenized(&self) -> bool {
+        self.receipt_mint != Pubkey::default()
+    }
+
+    // Tokenized positions lose their receipt once fully claimed, so nobody could unlock anything added after that
+    pub fn is_receipt_burned(&self) -> bool {
+        self.is_tokenized() && self.total_amount > 0 && self.locked_amount() == 0
+    }
+
+    pub fn is_delegate(&self, key: &Pubkey) -> bool {
+        self.delegate != Pubkey::default() && self.delegate == *key
+    }
+
+    // Checks that `holder` is entitled to the position, either as its owner or as the holder of its receipt.
+    // Receipts are passed as the last two `remaining_accounts`, the receipt mint followed by the holder's token account,
+    // and are returned so they can be burned once the position is fully claimed
+    pub fn authorize<'info>(&self, accounts: &[AccountInfo<'info>], holder: &Pubkey) -> Result<Option<(AccountInfo<'info>, Account<'info, TokenAccount>)>, ProgramError> {
+        if !self.is_tokenized() {
+            if self.owner != *holder {
+                return Err(PresaleError::NotPositionOwner.into());
+            }
+            return Ok(None);
+        }
+
+        if accounts.len() < 2 {
+            return Err(PresaleError::MissingVestingReceipt.into());
+        }
+        let receipt_mint = &accounts[accounts.len() - 2];
+        if *receipt_mint.key != self.receipt_mint || !receipt_mint.is_writable {
+            return Err(PresaleError::MissingVestingReceipt.into());
+        }
+
+        let receipt_account: Account<'info, TokenAccount> = Account::try_from(&accounts[accounts.len() - 1])?;
+        if receipt_account.mint != self.receipt_mint || receipt_account.owner != *holder || receipt_account.amount != 1 {
+            return Err(PresaleError::MissingVestingReceipt.into());
+        }
+        Ok(Some((receipt_mint.clone(), receipt_account)))
+    }
+
+    // Credits this account with the penalties redistributed since its locked amount last changed.
+    // Must be called before the locked amount changes, followed by `reset_penalty_debt` after
+    pub fn settle_penalty_rewards(&mut self, presale: &PresaleInfo) -> Result<(), ProgramError> {
+        let accrued = match (self.locked_amount() as u128).checked_mul(presale.penalty_per_fraction) {
+            Some(val) => val / PENALTY_PRECISION,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        let pending = accrued.saturating_sub(self.penalty_debt) as u64;
+        self.penalty_rewards = match self.penalty_rewards.checked_add(pending) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+    pub fn reset_penalty_debt(&mut self, presale: &PresaleInfo) -> Result<(), ProgramError> {
+        self.penalty_debt = match (self.locked_amount() as u128).checked_mul(presale.penalty_per_fraction) {
+            Some(val) => val / PENALTY_PRECISION,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+}
+
+// Counts every presale ever created, which numbers their addresses
+#[account]
+pub struct Registry {
+
+    pub presale_count: u64,
+
+    pub bump: u8,
+
+}
+
+// Lists the presales of a fraction mint so clients can find them without scanning the program,
+// `MAX_INDEXED_PRESALES` to a page
+#[account]
+pub struct PresaleIndex {
+
+    pub fraction_mint: Pubkey,
+
+    pub page: u32,
+
+    pub presales: Vec<Pubkey>,
+
+}
+
+// Platform settings shared by every presale, at a single PDA
+#[account]
+pub struct ProgramConfig {
+
+    pub admin: Pubkey,
+
+    pub fee_bps: u16, // fee recorded by presales created from now on
+
+    pub bump: u8,
+
+}
+
+// Platform token account receiving the fees of presales paid in `payment_mint`
+#[account]
+pub struct FeeRecipient {
+
+    pub payment_mint: Pubkey,
+
+    pub token_account: Pubkey,
+
 }
 
 #[account]
@@ -23,6 +225,16 @@ pub struct PresaleInfo {
 
     pub authority: Pubkey,
 
+    pub pending_authority: Pubkey, // proposed by `authority`, takes over once it accepts
+
+    pub operator: Pubkey, // manages the fractions for sale and starts the presale
+
+    pub treasurer: Pubkey, // the only key allowed to collect the raised funds
+
+    pub pauser: Pubkey, // can halt purchases, which only the operator can resume
+
+    pub paused: bool,
+
     pub fractions_sold: u64,
 
     pub fraction_mint: Pubkey,
@@ -33,6 +245,8 @@ pub struct PresaleInfo {
 
     pub price: u64,
 
+    pub start_price: u64, // price when the presale started, which later price changes are bounded by
+
     pub max_amount: u64,
 
     pub presale_start: u64, // signifies when the presale started
@@ -41,6 +255,668 @@ pub struct PresaleInfo {
 
     pub presale_end: u64, // amount of time before purchases can no longer be made
 
-    pub vesting_end: u64 // amount of time for vesting before unlock
+    pub vesting_end: u64, // amount of time for vesting before unlock
+
+    pub lot_duration: u64, // when non-zero, every purchase vests for this long from the time it was made
+
+    pub tge_timestamp: u64, // token generation event, from which the `tge_unlock_bps` share can be claimed
+
+    pub tge_unlock_bps: u16, // share of every purchase released at TGE, the remainder unlocks at `vesting_end`
+
+    pub vesting_start: u64, // set once by the authority after the presale ends when vesting is deferred
+
+    pub vesting_start_deadline: u64, // latest allowed vesting start, and the fallback start if it is never set
+
+    pub vesting_duration: u64, // time from the deferred vesting start until `vesting_end`
+
+    pub milestones: Vec<Milestone>, // when set, unlocks follow these milestones instead of the vesting timestamps
+
+    pub early_exit_penalty_bps: u16, // penalty for leaving vesting at `presale_start`, shrinking linearly to zero at `vesting_end`
+
+    pub penalty_to_pool: bool, // whether penalties are shared among the remaining buyers rather than put back up for sale
+
+    pub fractions_vesting: u64, // fractions locked across every vesting account
+
+    pub penalty_per_fraction: u128, // penalties redistributed per locked fraction, scaled by `PENALTY_PRECISION`
+
+    pub penalty_pool: u64, // fractions in `fraction_treasury` owed to buyers as redistributed penalties
+
+    pub lockup_options: Vec<LockupOption>, // lockups buyers can opt into for bonus fractions
+
+    pub transfer_requires_authority: bool, // whether the authority must co-sign vesting position transfers
+
+    pub vesting_receipts: bool, // whether every position is tokenized as a receipt before its first purchase
+
+    pub vesting_escrow: Pubkey, // when set, every position's fractions are pooled in this account instead of one per buyer
+
+    pub timelock_delay: u64, // when non-zero, admin changes have to be queued for this long before they apply
+
+    pub funds_destination: Pubkey, // wallet `collect_funds` pays out to, the treasurer when unset
+
+    pub queued_actions: u64, // number of actions ever queued, which seeds the next `QueuedAction`
+
+    pub beneficiaries: Vec<Beneficiary>, // when set, raised funds are split among these by `distribute_funds`
+
+    pub fee_bps: u16, // platform fee in force when the presale was created, taken out of the funds it raises
+
+    pub fee_account: Pubkey, // platform token account the fee is paid into, unset when there is no fee
+
+    pub unsold_destination: Pubkey, // fraction account `finalize_presale` returns unsold fractions to, they are burned when unset
+
+    pub finalized: bool,
+
+    pub open_positions: u64, // vesting positions that have not been closed yet
+
+    pub grant_allowance: u64, // fractions approved through the queue for grants and airdrops while a timelock is set
+
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Beneficiary {
+
+    pub token_account: Pubkey, // payment token account the share is paid into
+
+    pub share_bps: u16,
+
+}
+
+impl Beneficiary {
+
+    // Shares have to add up to the whole treasury, and each account can only appear once.
+    // An empty list is valid and leaves the funds to `collect_funds`
+    pub fn validate(beneficiaries: &[Beneficiary]) -> Result<(), ProgramError> {
+        if beneficiaries.is_empty() {
+            return Ok(());
+        }
+        if beneficiaries.len() > MAX_BENEFICIARIES {
+            return Err(PresaleError::InvalidBeneficiaries.into());
+        }
+        let mut total_bps: u64 = 0;
+        for (index, beneficiary) in beneficiaries.iter().enumerate() {
+            if beneficiary.share_bps == 0
+                || beneficiaries[..index].iter().any(|other| other.token_account == beneficiary.token_account)
+            {
+                return Err(PresaleError::InvalidBeneficiaries.into());
+            }
+            total_bps += beneficiary.share_bps as u64;
+        }
+        if total_bps != BASIS_POINTS {
+            return Err(PresaleError::InvalidBeneficiaries.into());
+        }
+        Ok(())
+    }
+
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct LockupOption {
+
+    pub multiplier_bps: u16, // vesting length relative to the base schedule, 10_000 being 1x
+
+    pub bonus_bps: u16, // extra fractions granted on every purchase made with this lockup
+
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Milestone {
+
+    pub unlock_bps: u16, // share of every position released by this milestone
+
+    pub deadline: u64, // the milestone unlocks automatically at this time if it was never marked as reached
+
+    pub reached: bool,
+
+}
+
+impl PresaleInfo {
+
+    pub fn is_vesting_pooled(&self) -> bool {
+        self.vesting_escrow != Pubkey::default()
+    }
+
+    pub fn is_vesting_deferred(&self) -> bool {
+        self.vesting_start_deadline > 0
+    }
+
+    // Applies the parameters that are not `None`. Once started, only changes in buyers' favour are allowed
+    pub fn update_params(
+        &mut self,
+        price: Option<u64>,
+        max_amount: Option<u64>,
+        presale_end: Option<u64>,
+        vesting_end: Option<u64>,
+        current_timestamp: u64
+    ) -> Result<(), ProgramError> {
+        // Deferred presales derive their vesting end from `set_vesting_start`
+        if vesting_end.is_some() && self.is_vesting_deferred() {
+            return Err(PresaleError::InvalidVestingSchedule.into());
+        }
+
+        // Lockup offers were priced against these dates, so they stay fixed once lockups are offered
+        if (presale_end.is_some() || vesting_end.is_some()) && !self.lockup_options.is_empty() {
+            return Err(PresaleError::InvalidVestingSchedule.into());
+        }
+
+        if self.started {
+            // The price can only drift a bounded share away from what buyers saw at the start
+            if let Some(price) = price {
+                let max_change = match (self.start_price as u128).checked_mul(MAX_PRICE_CHANGE_BPS as u128) {
+                    Some(val) => val / BASIS_POINTS as u128,
+                    None => return Err(PresaleError::NumericalOverflowError.into()),
+                };
+                let change = (price as i128 - self.start_price as i128).unsigned_abs();
+                if change > max_change {
+                    return Err(PresaleError::PriceChangeTooLarge.into());
+                }
+            }
+
+            // Buyers can be allowed more but never less
+            if let Some(max_amount) = max_amount {
+                if max_amount < self.max_amount {
+                    return Err(PresaleError::InvalidPresaleParams.into());
+                }
+            }
+
+            // The sale can only be extended while it is still open
+            if let Some(presale_end) = presale_end {
+                if presale_end < self.presale_end || current_timestamp >= self.presale_end {
+                    return Err(PresaleError::InvalidPresaleParams.into());
+                }
+            }
+
+            // Buyers who already purchased must never wait longer than they signed up for
+            if let Some(vesting_end) = vesting_end {
+                if vesting_end > self.vesting_end {
+                    return Err(PresaleError::InvalidPresaleParams.into());
+                }
+            }
+        }
+
+        if let Some(price) = price {
+            self.price = price;
+        }
+        if let Some(max_amount) = max_amount {
+            self.max_amount = max_amount;
+        }
+        if let Some(presale_end) = presale_end {
+            self.presale_end = presale_end;
+        }
+        if let Some(vesting_end) = vesting_end {
+            self.vesting_end = vesting_end;
+        }
+
+        // The sale must still close before vesting can begin
+        if self.is_vesting_deferred() {
+            if self.presale_end > self.vesting_start_deadline {
+                return Err(PresaleError::InvalidVestingSchedule.into());
+            }
+        } else if self.lot_duration == 0
+            && (self.presale_end > self.vesting_end || self.tge_timestamp > self.vesting_end)
+        {
+            return Err(PresaleError::InvalidVestingSchedule.into());
+        }
+
+        // Milestones only start paying out once the sale is over, like `set_milestones` requires
+        if self.milestones.iter().any(|milestone| milestone.deadline <= self.presale_end) {
+            return Err(PresaleError::InvalidMilestones.into());
+        }
+        Ok(())
+    }
+
+    // Grants and airdrops take fractions out of the treasury, so under a timelock they can only
+    // spend what an `ApproveGrants` action has approved
+    pub fn spend_grant_allowance(&mut self, amount: u64) -> Result<(), ProgramError> {
+        if self.timelock_delay == 0 {
+            return Ok(());
+        }
+        self.grant_allowance = match self.grant_allowance.checked_sub(amount) {
+            Some(val) => val,
+            None => return Err(PresaleError::GrantNotApproved.into()),
+        };
+        Ok(())
+    }
+
+    pub fn position_opened(&mut self) -> Result<(), ProgramError> {
+        self.open_positions = match self.open_positions.checked_add(1) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+    pub fn funds_recipient(&self) -> Pubkey {
+        if self.funds_destination == Pubkey::default() { self.treasurer } else { self.funds_destination }
+    }
+
+    pub fn set_roles(&mut self, operator: Pubkey, treasurer: Pubkey, pauser: Pubkey) {
+        self.operator = operator;
+        self.treasurer = treasurer;
+        self.pauser = pauser;
+    }
+
+    // Hands the presale to `new_authority`, along with every role still held by the authority it replaces
+    pub fn transfer_authority(&mut self, new_authority: Pubkey) {
+        let old_authority = self.authority;
+        for role in [&mut self.operator, &mut self.treasurer, &mut self.pauser] {
+            if *role == old_authority {
+                *role = new_authority;
+            }
+        }
+        self.authority = new_authority;
+        self.pending_authority = Pubkey::default();
+    }
+
+    // Fractions in the treasury that are not owed to anyone
+    pub fn available_fractions(&self, treasury_amount: u64) -> u64 {
+        treasury_amount.saturating_sub(self.penalty_pool)
+    }
+
+    // Returns the TGE and vesting end timestamps, falling back to `vesting_start_deadline`
+    // as the vesting start if a deferred presale never had it set
+    pub fn vesting_timestamps(&self) -> Result<(u64, u64), ProgramError> {
+        if self.is_vesting_deferred() && self.vesting_start == 0 {
+            let vesting_end = match self.vesting_start_deadline.checked_add(self.vesting_duration) {
+                Some(val) => val,
+                None => return Err(PresaleError::NumericalOverflowError.into()),
+            };
+            return Ok((self.vesting_start_deadline, vesting_end));
+        }
+        Ok((self.tge_timestamp, self.vesting_end))
+    }
+
+    // Stretches the vesting period ending at `vesting_end` by a buyer's lockup multiplier.
+    // Vesting is counted from the vesting start when deferred, and from the presale end otherwise
+    pub fn lockup_vesting_end(&self, tge_timestamp: u64, vesting_end: u64, lockup_multiplier_bps: u16) -> Result<u64, ProgramError> {
+        if lockup_multiplier_bps as u64 <= BASIS_POINTS {
+            return Ok(vesting_end);
+        }
+        let vesting_start = if self.is_vesting_deferred() { tge_timestamp } else { self.presale_end };
+        let extended = match (vesting_end.saturating_sub(vesting_start) as u128).checked_mul(lockup_multiplier_bps as u128) {
+            Some(val) => val / BASIS_POINTS as u128,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        match (vesting_start as u128).checked_add(extended) {
+            Some(val) if val <= u64::MAX as u128 => Ok(val as u64),
+            _ => Err(PresaleError::NumericalOverflowError.into()),
+        }
+    }
+
+    // Amount out of `total` that has vested by `timestamp` for a buyer with the given lockup
+    pub fn vested_amount(&self, total: u64, lockup_multiplier_bps: u16, timestamp: u64) -> Result<u64, ProgramError> {
+        if !self.milestones.is_empty() {
+            let unlocked_bps: u16 = self.milestones.iter()
+                .filter(|milestone| milestone.reached || timestamp >= milestone.deadline)
+                .map(|milestone| milestone.unlock_bps)
+                .sum();
+            return basis_points_of(total, unlocked_bps);
+        }
+
+        let (tge_timestamp, vesting_end) = self.vesting_timestamps()?;
+        let vesting_end = self.lockup_vesting_end(tge_timestamp, vesting_end, lockup_multiplier_bps)?;
+        if timestamp >= vesting_end {
+            return Ok(total);
+        }
+        if self.tge_unlock_bps > 0 && timestamp >= tge_timestamp {
+            return basis_points_of(total, self.tge_unlock_bps);
+        }
+        Ok(0)
+    }
 
-}
\ No newline at end of file
+    // Penalty in basis points for leaving vesting at `timestamp`
+    pub fn early_exit_penalty_bps_at(&self, timestamp: u64) -> Result<u16, ProgramError> {
+        let (_, vesting_end) = self.vesting_timestamps()?;
+        if timestamp >= vesting_end {
+            return Ok(0);
+        }
+        if timestamp <= self.presale_start {
+            return Ok(self.early_exit_penalty_bps);
+        }
+        let remaining = (vesting_end - timestamp) as u128;
+        let period = (vesting_end - self.presale_start) as u128;
+        Ok((self.early_exit_penalty_bps as u128 * remaining / period) as u16)
+    }
+
+    // Adds `amount` to a position's locked balance, crediting the penalties redistributed before it grew
+    pub fn vest(&mut self, vesting_info: &mut VestingInfo, amount: u64) -> Result<(), ProgramError> {
+        vesting_info.settle_penalty_rewards(self)?;
+        vesting_info.total_amount = match vesting_info.total_amount.checked_add(amount) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        vesting_info.reset_penalty_debt(self)?;
+
+        self.fractions_vesting = match self.fractions_vesting.checked_add(amount) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+    // Takes `amount` out of the fractions vesting, along with the `penalty_rewards` paid for it. Once nothing
+    // is vesting nobody can be owed a penalty share any more, so what the pool still holds is rounding left over
+    pub fn release_vesting(&mut self, amount: u64, penalty_rewards: u64) {
+        self.fractions_vesting = self.fractions_vesting.saturating_sub(amount);
+        self.penalty_pool = if self.fractions_vesting == 0 {
+            0
+        } else {
+            self.penalty_pool.saturating_sub(penalty_rewards)
+        };
+    }
+
+    // Shares `penalty` out among every fraction still locked. Only what the per-fraction rate can pay out
+    // is reserved in `penalty_pool`, so the rounding remainder, or the whole penalty when nobody is left
+    // to share it with, stays in the treasury up for sale
+    pub fn redistribute_penalty(&mut self, penalty: u64) -> Result<(), ProgramError> {
+        if self.fractions_vesting == 0 {
+            return Ok(());
+        }
+        let per_fraction = match (penalty as u128).checked_mul(PENALTY_PRECISION) {
+            Some(val) => val / self.fractions_vesting as u128,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        let shared = match per_fraction.checked_mul(self.fractions_vesting as u128) {
+            Some(val) => (val / PENALTY_PRECISION) as u64,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        self.penalty_per_fraction = match self.penalty_per_fraction.checked_add(per_fraction) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        self.penalty_pool = match self.penalty_pool.checked_add(shared) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        Ok(())
+    }
+
+}
+
+pub fn basis_points_of(amount: u64, bps: u16) -> Result<u64, ProgramError> {
+    match (amount as u128).checked_mul(bps as u128) {
+        Some(val) => Ok((val / BASIS_POINTS as u128) as u64),
+        None => Err(PresaleError::NumericalOverflowError.into()),
+    }
+}
+
+// Creates `target` the way Anchor's `init` does, by topping it up to rent exemption and then allocating
+// and assigning it, so that lamports sent to the address beforehand can't block its creation
+pub fn create_account<'info>(
+    payer: &AccountInfo<'info>,
+    target: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    rent: &Rent,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]]
+) -> ProgramResult {
+    let required_lamports = rent.minimum_balance(space).saturating_sub(target.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, target.key, required_lamports),
+            &[payer.clone(), target.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(target.key, space as u64),
+        &[target.clone(), system_program.clone()],
+        signer_seeds,
+    )?;
+    invoke_signed(
+        &system_instruction::assign(target.key, owner),
+        &[target.clone(), system_program.clone()],
+        signer_seeds,
+    )
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct AirdropAllocation {
+
+    pub amount: u64,
+
+    pub vesting_pda_bump: u8,
+
+    pub vesting_account_bump: u8, // only used when the recipient's position has to be created
+
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct VestingLot {
+
+    pub amount: u64,
+
+    pub purchase_time: u64,
+
+    pub unlock_time: u64,
+
+}
+
+#[account]
+pub struct VestingLots {
+
+    pub vesting_info: Pubkey, // the `VestingInfo` these lots belong to
+
+    pub lots: Vec<VestingLot>,
+
+}
+
+impl VestingLots {
+
+    // Lots are passed in `remaining_accounts` since only presales with a `lot_duration` use them
+    pub fn load<'info>(accounts: &[AccountInfo<'info>], vesting_info: &Pubkey) -> Result<Account<'info, VestingLots>, ProgramError> {
+        let lots_info = match accounts.first() {
+            Some(info) => info,
+            None => return Err(PresaleError::MissingVestingLots.into()),
+        };
+        if !lots_info.is_writable {
+            return Err(PresaleError::MissingVestingLots.into());
+        }
+
+        let vesting_lots: Account<'info, VestingLots> = Account::try_from(lots_info)?;
+        if vesting_lots.vesting_info != *vesting_info {
+            return Err(PresaleError::MissingVestingLots.into());
+        }
+        Ok(vesting_lots)
+    }
+
+    // Like `load`, but first opens the lots at their PDA, paid for by the buyer who seeds it, when they don't exist yet
+    pub fn load_or_open<'info>(
+        accounts: &[AccountInfo<'info>],
+        vesting_info: &Pubkey,
+        signer: &AccountInfo<'info>,
+        presale: &Pubkey,
+        system_program: &AccountInfo<'info>,
+        rent: &Rent,
+        program_id: &Pubkey
+    ) -> Result<Account<'info, VestingLots>, ProgramError> {
+        let lots_info = match accounts.first() {
+            Some(info) if info.data_is_empty() => info,
+            _ => return VestingLots::load(accounts, vesting_info),
+        };
+
+        let (lots_key, lots_bump) = Pubkey::find_program_address(
+            &[b"lots".as_ref(), signer.key.as_ref(), presale.as_ref(), program_id.as_ref()],
+            program_id
+        );
+        if *lots_info.key != lots_key || !lots_info.is_writable {
+            return Err(PresaleError::MissingVestingLots.into());
+        }
+        let lots_seeds: &[&[u8]] = &[b"lots".as_ref(), signer.key.as_ref(), presale.as_ref(), program_id.as_ref(), &[lots_bump]];
+        create_account(signer, lots_info, system_program, rent, 8 + VESTING_LOTS_SIZE, program_id, &[lots_seeds])?;
+
+        let vesting_lots = VestingLots {
+            vesting_info: *vesting_info,
+            lots: Vec::new(),
+        };
+        vesting_lots.try_serialize(&mut &mut lots_info.try_borrow_mut_data()?[..])?;
+        VestingLots::load(accounts, vesting_info)
+    }
+
+    // Records `amount` as a new lot vesting for `lot_duration` from `purchase_time`
+    pub fn push_lot(&mut self, amount: u64, purchase_time: u64, lot_duration: u64) -> Result<(), ProgramError> {
+        if self.lots.len() >= MAX_VESTING_LOTS {
+            self.merge_matured(purchase_time)?;
+        }
+        if self.lots.len() >= MAX_VESTING_LOTS {
+            return Err(PresaleError::TooManyVestingLots.into());
+        }
+        let unlock_time = match purchase_time.checked_add(lot_duration) {
+            Some(val) => val,
+            None => return Err(PresaleError::NumericalOverflowError.into()),
+        };
+        self.lots.push(VestingLot {
+            amount,
+            purchase_time,
+            unlock_time,
+        });
+        Ok(())
+    }
+
+    // Folds the lots matured by `timestamp` into a single one, which stays matured
+    fn merge_matured(&mut self, timestamp: u64) -> Result<(), ProgramError> {
+        let mut merged: Option<VestingLot> = None;
+        for lot in self.lots.iter().filter(|lot| lot.unlock_time <= timestamp) {
+            merged = Some(match merged {
+                Some(val) => VestingLot {
+                    amount: val.amount.checked_add(lot.amount).ok_or(PresaleError::NumericalOverflowError)?,
+                    purchase_time: val.purchase_time.min(lot.purchase_time),
+                    unlock_time: val.unlock_time.max(lot.unlock_time),
+                },
+                None => *lot,
+            });
+        }
+        if let Some(merged) = merged {
+            self.lots.retain(|lot| lot.unlock_time > timestamp);
+            self.lots.insert(0, merged);
+        }
+        Ok(())
+    }
+
+    // Amount held by the lots that have matured by `timestamp`
+    pub fn matured_amount(&self, timestamp: u64) -> Result<u64, ProgramError> {
+        let mut matured: u64 = 0;
+        for lot in self.lots.iter().filter(|lot| lot.unlock_time <= timestamp) {
+            matured = matured.checked_add(lot.amount).ok_or(PresaleError::NumericalOverflowError)?;
+        }
+        Ok(matured)
+    }
+
+    // Takes `amount` out of the lots matured by `timestamp`, oldest first, and removes the lots left empty.
+    // `amount` must not exceed `matured_amount`
+    pub fn release(&mut self, timestamp: u64, amount: u64) {
+        let mut remaining = amount;
+        for lot in self.lots.iter_mut().filter(|lot| lot.unlock_time <= timestamp) {
+            let released = lot.amount.min(remaining);
+            lot.amount -= released;
+            remaining -= released;
+        }
+        self.lots.retain(|lot| lot.amount > 0);
+    }
+
+}
+
+#[account]
+pub struct Multisig {
+
+    pub presale_account: Pubkey, // presale this multisig governs, which seeds its PDA
+
+    pub owners: Vec<Pubkey>,
+
+    pub threshold: u8, // approvals needed before a transaction can be executed
+
+    pub owner_set_seqno: u32, // bumped whenever the owners change, so pending approvals can't carry over
+
+}
+
+impl Multisig {
+
+    pub fn validate_owners(owners: &[Pubkey], threshold: u8) -> Result<(), ProgramError> {
+        if owners.is_empty() || owners.len() > MAX_MULTISIG_OWNERS {
+            return Err(PresaleError::InvalidMultisigOwners.into());
+        }
+        for (index, owner) in owners.iter().enumerate() {
+            if owners[..index].contains(owner) {
+                return Err(PresaleError::InvalidMultisigOwners.into());
+            }
+        }
+        if threshold == 0 || threshold as usize > owners.len() {
+            return Err(PresaleError::InvalidMultisigThreshold.into());
+        }
+        Ok(())
+    }
+
+    pub fn owner_index(&self, key: &Pubkey) -> Result<usize, ProgramError> {
+        match self.owners.iter().position(|owner| owner == key) {
+            Some(index) => Ok(index),
+            None => Err(PresaleError::NotMultisigOwner.into()),
+        }
+    }
+
+}
+
+#[account]
+pub struct MultisigTransaction {
+
+    pub multisig: Pubkey,
+
+    pub program_id: Pubkey, // program the instruction is executed against
+
+    pub accounts: Vec<TransactionAccount>,
+
+    pub data: Vec<u8>,
+
+    pub signers: Vec<bool>, // approvals, indexed like the multisig's owners
+
+    pub did_execute: bool,
+
+    pub owner_set_seqno: u32, // owner set the approvals were given under
+
+    pub proposer: Pubkey, // paid for the account, and gets its rent back once it is executed
+
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TransactionAccount {
+
+    pub pubkey: Pubkey,
+
+    pub is_signer: bool,
+
+    pub is_writable: bool,
+
+}
+
+#[account]
+pub struct QueuedAction {
+
+    pub presale_account: Pubkey,
+
+    pub action: AdminAction,
+
+    pub queued_at: u64, // the presale's current `timelock_delay` has to pass from here before the action can be executed
+
+    pub payer: Pubkey, // paid for the account, and gets its rent back once it is executed or cancelled
+
+}
+
+// Admin changes that have to wait out `timelock_delay` so buyers can react to them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum AdminAction {
+    RemoveFractions { amount: u64 },
+    UpdatePresaleParams { price: Option<u64>, max_amount: Option<u64>, presale_end: Option<u64>, vesting_end: Option<u64> },
+    SetFundsDestination { funds_destination: Pubkey },
+    SetTimelockDelay { timelock_delay: u64 },
+    SetRoles { operator: Pubkey, treasurer: Pubkey, pauser: Pubkey },
+    SetBeneficiaries { beneficiaries: Vec<Beneficiary> },
+    ApproveGrants { amount: u64 },
+}
+
+impl AdminAction {
+    // Beneficiaries are the treasurer's to change, treasury fractions the operator's, everything else the presale authority's
+    pub fn role(&self, presale_account: &PresaleInfo) -> Pubkey {
+        match self {
+            AdminAction::SetBeneficiaries { .. } => presale_account.treasurer,
+            AdminAction::RemoveFractions { .. } | AdminAction::ApproveGrants { .. } => presale_account.operator,
+            _ => presale_account.authority,
+        }
+    }
+}
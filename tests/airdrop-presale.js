@@ -0,0 +1,99 @@
+const { assert } = require('chai');
+const {
+	anchor, splToken, BN, SystemProgram, SYSVAR_RENT_PUBKEY, Keypair, Transaction, provider,
+	payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance, setupPresale,
+	unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with batch airdrops', () => {
+
+	var presale;
+	var recipient;
+	var recipientVestingPDA;
+	var recipientVestingPDABump;
+	var recipientVestingAccount;
+	var vestingEnd;
+
+	it('Initialise presale', async () => {
+		vestingEnd = getCurrentTimestamp(20);
+		presale = await setupPresale({ vestingEnd });
+		recipient = Keypair.generate();
+	});
+
+	it('Airdrop into an existing position and a new one', async () => {
+		let recipientVestingAccountBump;
+		[recipientVestingPDA, recipientVestingPDABump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("vesting"), recipient.publicKey.toBuffer(), presale.account.publicKey.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+		[recipientVestingAccount, recipientVestingAccountBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("vesting_account"), recipientVestingPDA.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+
+		// Lamports sent to the new position's addresses beforehand must not block its creation
+		await provider.send(new Transaction().add(
+			SystemProgram.transfer({ fromPubkey: payerKey, toPubkey: recipientVestingPDA, lamports: 1_000_000 }),
+			SystemProgram.transfer({ fromPubkey: payerKey, toPubkey: recipientVestingAccount, lamports: 1_000_000 }),
+		));
+
+		await program.rpc.airdropFractions(presale.pdaBump, [
+			{ amount: new BN(100 * 1e9), vestingPdaBump: presale.vestingPDABump, vestingAccountBump: 0 },
+			{ amount: new BN(200 * 1e9), vestingPdaBump: recipientVestingPDABump, vestingAccountBump: recipientVestingAccountBump },
+		], {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				fractionMint: presale.fractionMint.publicKey,
+				presalePda: presale.pda,
+				operator: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				rent: SYSVAR_RENT_PUBKEY,
+				systemProgram: SystemProgram.programId,
+			},
+			remainingAccounts: [
+				{ pubkey: payerKey, isWritable: false, isSigner: false },
+				{ pubkey: presale.vestingPDA, isWritable: true, isSigner: false },
+				{ pubkey: presale.vestingAccount.publicKey, isWritable: true, isSigner: false },
+				{ pubkey: recipient.publicKey, isWritable: false, isSigner: false },
+				{ pubkey: recipientVestingPDA, isWritable: true, isSigner: false },
+				{ pubkey: recipientVestingAccount, isWritable: true, isSigner: false },
+			]
+		});
+
+		let vestingInfo = await program.account.vestingInfo.fetch(presale.vestingPDA);
+		assert.equal(vestingInfo.totalAmount.toString(), (100 * 1e9).toString());
+
+		vestingInfo = await program.account.vestingInfo.fetch(recipientVestingPDA);
+		assert.equal(vestingInfo.signer.toString(), recipient.publicKey.toString());
+		assert.equal(vestingInfo.owner.toString(), recipient.publicKey.toString());
+		assert.equal(vestingInfo.vestingAccount.toString(), recipientVestingAccount.toString());
+		assert.equal(vestingInfo.totalAmount.toString(), (200 * 1e9).toString());
+		assert.equal((await getTokenAccountBalance(recipientVestingAccount)).toString(), (200 * 1e9).toString());
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.fractionsVesting.toString(), (300 * 1e9).toString());
+	});
+
+	it('Airdropped fractions are locked until vesting ends', async () => {
+		let toAccount = await presale.fractionMint.createAccount(recipient.publicKey);
+		let position = {
+			user: recipient,
+			vestingAccount: { publicKey: recipientVestingAccount },
+			vestingPDA: recipientVestingPDA,
+			vestingPDABump: recipientVestingPDABump,
+			remainingAccounts: [],
+		};
+		try {
+			await unlockFractions(presale, toAccount, position);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting period has not finished");
+		}
+
+		await sleepUntil(vestingEnd);
+		await unlockFractions(presale, toAccount, position);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (200 * 1e9).toString());
+	});
+
+});
@@ -0,0 +1,76 @@
+const { assert } = require('chai');
+const {
+	anchor, Keypair, payerKey, program, getCurrentTimestamp, setupPresale,
+} = require('./helpers');
+
+describe('fraction-presale authority rotation', () => {
+
+	var presale;
+	const newAuthority = Keypair.generate();
+
+	const acceptAuthority = async (signer) => {
+		await program.rpc.acceptAuthority({
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				pendingAuthority: signer.publicKey,
+			},
+			signers: [signer]
+		});
+	}
+
+	it('Initialise presale', async () => {
+		presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+	});
+
+	it('Authority can be proposed and replaced before acceptance', async () => {
+		await program.rpc.proposeAuthority(Keypair.generate().publicKey, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey,
+			},
+		});
+		await program.rpc.proposeAuthority(newAuthority.publicKey, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey,
+			},
+		});
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.authority.toString(), payerKey.toString());
+		assert.equal(presaleInfo.pendingAuthority.toString(), newAuthority.publicKey.toString());
+	});
+
+	it('Only the pending authority can accept', async () => {
+		try {
+			await acceptAuthority(Keypair.generate());
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The signer is not the pending authority of the presale");
+		}
+	});
+
+	it('Pending authority takes over once it accepts', async () => {
+		await acceptAuthority(newAuthority);
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.authority.toString(), newAuthority.publicKey.toString());
+		assert.equal(presaleInfo.pendingAuthority.toString(), new anchor.web3.PublicKey(0).toString());
+		assert.equal(presaleInfo.operator.toString(), newAuthority.publicKey.toString());
+		assert.equal(presaleInfo.treasurer.toString(), newAuthority.publicKey.toString());
+		assert.equal(presaleInfo.pauser.toString(), newAuthority.publicKey.toString());
+
+		try {
+			await program.rpc.proposeAuthority(payerKey, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					authority: payerKey,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+	});
+
+});
@@ -0,0 +1,91 @@
+const { assert } = require('chai');
+const {
+	splToken, BN, Keypair, payerKey, program, getCurrentTimestamp, createNativeTokenAccount,
+	getTokenAccountBalance, setupPresale, purchaseFractions,
+} = require('./helpers');
+
+describe('fraction-presale splitting funds among beneficiaries', () => {
+
+	var presale, beneficiaries;
+	const shares = [3333, 3333, 3334];
+
+	const distributeFunds = async (accounts) => {
+		await program.rpc.distributeFunds(presale.pdaBump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				paymentTreasury: presale.paymentTreasury.publicKey,
+				presalePda: presale.pda,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts: accounts.map(pubkey => ({ pubkey, isSigner: false, isWritable: true })),
+		});
+	}
+
+	it('Initialise presale with beneficiaries', async () => {
+		presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+		beneficiaries = [];
+		for (let i = 0; i < shares.length; i++) {
+			beneficiaries.push(await createNativeTokenAccount(0, Keypair.generate()));
+		}
+
+		try {
+			await program.rpc.setBeneficiaries([{ tokenAccount: beneficiaries[0], shareBps: 5000 }], {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					treasurer: payerKey,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Beneficiary shares must add up to 10000 basis points across distinct token accounts");
+		}
+
+		await program.rpc.setBeneficiaries(beneficiaries.map((tokenAccount, i) => ({ tokenAccount, shareBps: shares[i] })), {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				treasurer: payerKey,
+			},
+		});
+		await purchaseFractions(presale, 7);
+	});
+
+	it('Funds can no longer be collected whole', async () => {
+		let toAccount = await createNativeTokenAccount(0);
+		try {
+			await program.rpc.collectFunds(presale.pdaBump, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					paymentTreasury: presale.paymentTreasury.publicKey,
+					toAccount,
+					presalePda: presale.pda,
+					treasurer: payerKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Raised funds are split among the beneficiaries by `distribute_funds`");
+		}
+	});
+
+	it('Anyone can split the treasury, with the remainder going to the first beneficiary', async () => {
+		try {
+			await distributeFunds([beneficiaries[1], beneficiaries[0], beneficiaries[2]]);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The beneficiary token accounts do not match the presale's beneficiaries");
+		}
+
+		let total = new BN((await getTokenAccountBalance(presale.paymentTreasury.publicKey)).toString());
+		let expected = shares.map(bps => total.muln(bps).divn(10_000));
+		let remainder = expected.reduce((left, share) => left.sub(share), total);
+		expected[0] = expected[0].add(remainder);
+
+		await distributeFunds(beneficiaries);
+		for (let i = 0; i < beneficiaries.length; i++) {
+			assert.equal((await getTokenAccountBalance(beneficiaries[i])).toString(), expected[i].toString());
+		}
+		assert.equal((await getTokenAccountBalance(presale.paymentTreasury.publicKey)).toString(), "0");
+	});
+
+});
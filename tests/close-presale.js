@@ -0,0 +1,116 @@
+const { assert } = require('chai');
+const {
+	splToken, connection, payer, payerKey, program, getCurrentTimestamp, sleepUntil, setupPresale,
+	purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale closing', () => {
+
+	const closePresale = async (presale) => {
+		await program.rpc.closePresale(presale.pdaBump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				presaleIndex: presale.presaleIndex,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				paymentTreasury: presale.paymentTreasury.publicKey,
+				accessTreasury: presale.accessTreasury.publicKey,
+				fractionMint: presale.fractionMint.publicKey,
+				accessMint: presale.accessMint.publicKey,
+				presalePda: presale.pda,
+				authority: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts: presale.vestingEscrow
+				? [{ pubkey: presale.vestingEscrow, isWritable: true, isSigner: false }]
+				: [],
+		});
+	}
+
+	const finalizePresale = async (presale) => {
+		await program.rpc.finalizePresale(presale.pdaBump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				fractionMint: presale.fractionMint.publicKey,
+				presalePda: presale.pda,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			}
+		});
+	}
+
+	it('Only fractions still vesting hold the presale open, not empty positions', async () => {
+		let presaleEnd = getCurrentTimestamp(10);
+		let vestingEnd = getCurrentTimestamp(20);
+		let presale = await setupPresale({ presaleEnd, vestingEnd });
+		await purchaseFractions(presale, 100);
+		await sleepUntil(presaleEnd);
+		await finalizePresale(presale);
+
+		try {
+			await closePresale(presale);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Fractions are still vesting or owed to buyers");
+		}
+
+		await sleepUntil(vestingEnd);
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount);
+		await closePresale(presale);
+		assert.equal(await connection.getAccountInfo(presale.account.publicKey), null);
+
+		// The emptied position is still its owner's to close
+		await program.rpc.closeVestingAccount(presale.vestingPDABump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				vestingAccount: presale.vestingAccount.publicKey,
+				toAccount,
+				userVestingPda: presale.vestingPDA,
+				signer: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+		});
+		assert.equal(await connection.getAccountInfo(presale.vestingPDA), null);
+		assert.equal(await connection.getAccountInfo(presale.vestingAccount.publicKey), null);
+	});
+
+	it('Fractions left in the pooled escrow are released on close', async () => {
+		let presaleEnd = getCurrentTimestamp(10);
+		let presale = await setupPresale({ presaleEnd, vestingEnd: getCurrentTimestamp(20), pooled: true });
+		await presale.fractionMint.transfer(presale.payerFractionAccount, presale.vestingEscrow, payer, [], 5);
+		let supplyBefore = (await presale.fractionMint.getMintInfo()).supply;
+
+		await sleepUntil(presaleEnd);
+		await finalizePresale(presale);
+		await closePresale(presale);
+
+		assert.equal(await connection.getAccountInfo(presale.vestingEscrow), null);
+		let supplyAfter = (await presale.fractionMint.getMintInfo()).supply;
+		assert.ok(supplyBefore.sub(supplyAfter).gten(5));
+	});
+
+	it('A finalized presale closes and hands its rent back', async () => {
+		let presaleEnd = getCurrentTimestamp(10);
+		let presale = await setupPresale({ presaleEnd, vestingEnd: getCurrentTimestamp(20) });
+		try {
+			await closePresale(presale);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale must be finalized before it can be closed");
+		}
+
+		await sleepUntil(presaleEnd);
+		await finalizePresale(presale);
+		let balanceBefore = await connection.getBalance(payerKey);
+		await closePresale(presale);
+		assert.ok(await connection.getBalance(payerKey) > balanceBefore);
+
+		assert.equal(await connection.getAccountInfo(presale.account.publicKey), null);
+		assert.equal(await connection.getAccountInfo(presale.fractionTreasury.publicKey), null);
+		assert.equal(await connection.getAccountInfo(presale.paymentTreasury.publicKey), null);
+		assert.equal(await connection.getAccountInfo(presale.accessTreasury.publicKey), null);
+		let indexInfo = await program.account.presaleIndex.fetch(presale.presaleIndex);
+		assert.deepEqual(indexInfo.presales, []);
+	});
+
+});
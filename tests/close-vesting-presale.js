@@ -0,0 +1,58 @@
+const { assert } = require('chai');
+const {
+	splToken, connection, payer, payerKey, program, getCurrentTimestamp, sleepUntil,
+	getTokenAccountBalance, setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale closing vesting accounts', () => {
+
+	var presale;
+	var vestingEnd;
+
+	const closeVestingAccount = async (toAccount) => {
+		await program.rpc.closeVestingAccount(presale.vestingPDABump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				vestingAccount: presale.vestingAccount.publicKey,
+				toAccount: toAccount,
+				userVestingPda: presale.vestingPDA,
+				signer: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts: presale.remainingAccounts,
+		});
+	}
+
+	it('Initialise presale and purchase', async () => {
+		vestingEnd = getCurrentTimestamp(15);
+		presale = await setupPresale({ vestingEnd });
+		await purchaseFractions(presale, 1_000);
+	});
+
+	it('Cannot close while fractions are still vesting', async () => {
+		try {
+			await closeVestingAccount(presale.payerFractionAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting account still holds fractions that have not been claimed");
+		}
+	});
+
+	it('Close both accounts after a full unlock', async () => {
+		await sleepUntil(vestingEnd);
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount);
+		let unlocked = await getTokenAccountBalance(toAccount);
+
+		// Fractions sent straight to the vesting account don't block the close, they go to the owner
+		await presale.fractionMint.transfer(presale.payerFractionAccount, presale.vestingAccount.publicKey, payer, [], 5);
+
+		let balanceBefore = await connection.getBalance(payerKey);
+		await closeVestingAccount(toAccount);
+		assert.isTrue((await connection.getBalance(payerKey)) > balanceBefore);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), unlocked.addn(5).toString());
+		assert.equal(await connection.getAccountInfo(presale.vestingPDA), null);
+		assert.equal(await connection.getAccountInfo(presale.vestingAccount.publicKey), null);
+	});
+
+});
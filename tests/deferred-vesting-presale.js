@@ -0,0 +1,94 @@
+const { assert } = require('chai');
+const {
+	BN, Keypair, payer, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance,
+	setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with a deferred vesting start', () => {
+
+	const tgeUnlockBps = 1_000;
+	const vestingDuration = 5;
+	var presale;
+	var presaleEnd;
+
+	const setVestingStart = async (vestingStart, user=payer) => {
+		await program.rpc.setVestingStart(new BN(vestingStart), {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: user.publicKey
+			},
+			signers: [user]
+		});
+	}
+
+	it('Initialise presale without vesting timestamps', async () => {
+		presaleEnd = getCurrentTimestamp(15);
+		presale = await setupPresale({ presaleEnd, tgeUnlockBps, vestingStartDeadline: presaleEnd + 100, vestingDuration });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.vestingEnd.toString(), '0');
+		assert.equal(presaleInfo.vestingStart.toString(), '0');
+		await purchaseFractions(presale, 1_000);
+	});
+
+	it('Cannot set the vesting start before the presale ends', async () => {
+		try {
+			await setVestingStart(presaleEnd + 1);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting start must be between the presale end and the committed deadline");
+		}
+	});
+
+	it('Cannot set the vesting start past the committed deadline', async () => {
+		await sleepUntil(presaleEnd);
+		try {
+			await setVestingStart(presaleEnd + 101);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting start must be between the presale end and the committed deadline");
+		}
+	});
+
+	it('Only authority can set the vesting start', async () => {
+		let badActor = Keypair.generate();
+		try {
+			await setVestingStart(getCurrentTimestamp(), badActor);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+	});
+
+	it('Set vesting start', async () => {
+		let vestingStart = getCurrentTimestamp(2);
+		await setVestingStart(vestingStart);
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.vestingStart.toString(), vestingStart.toString());
+		assert.equal(presaleInfo.tgeTimestamp.toString(), vestingStart.toString());
+		assert.equal(presaleInfo.vestingEnd.toString(), (vestingStart + vestingDuration).toString());
+
+		try {
+			await setVestingStart(vestingStart);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting start has already been set");
+		}
+
+		await sleepUntil(vestingStart + vestingDuration);
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_000 * 1e9).toString());
+	});
+
+	it('Buyers can unlock after the fallback deadline if the start is never set', async () => {
+		let fallbackEnd = getCurrentTimestamp(15);
+		let fallbackPresale = await setupPresale({ presaleEnd: fallbackEnd, vestingStartDeadline: fallbackEnd + 1, vestingDuration });
+		await purchaseFractions(fallbackPresale, 1_000);
+
+		await sleepUntil(fallbackEnd + 1 + vestingDuration);
+		let toAccount = await fallbackPresale.fractionMint.createAccount(payerKey);
+		await unlockFractions(fallbackPresale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_000 * 1e9).toString());
+	});
+
+});
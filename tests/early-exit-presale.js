@@ -0,0 +1,81 @@
+const { assert } = require('chai');
+const {
+	BN, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance, createBuyer,
+	setupPresale, purchaseFractions, unlockFractions, exitVestingEarly,
+} = require('./helpers');
+
+describe('fraction-presale with early exits', () => {
+
+	const penaltyBps = 5_000;
+	var presale;
+	var buyer;
+	var vestingEnd;
+
+	it('Initialise presale with a penalty shared among remaining buyers', async () => {
+		vestingEnd = getCurrentTimestamp(40);
+		presale = await setupPresale({ vestingEnd, earlyExitPenaltyBps: penaltyBps, penaltyToPool: true });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.earlyExitPenaltyBps, penaltyBps);
+		assert.equal(presaleInfo.penaltyToPool, true);
+
+		buyer = await createBuyer(presale);
+		await purchaseFractions(presale, 1_000);
+		await purchaseFractions(presale, 1_000, buyer);
+	});
+
+	it('Exiting early releases fractions minus a shrinking penalty', async () => {
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		let toAccount = await presale.fractionMint.createAccount(buyer.user.publicKey);
+		await exitVestingEarly(presale, toAccount, buyer);
+
+		let released = await getTokenAccountBalance(toAccount);
+		let penalty = new BN(1_000 * 1e9).sub(released);
+		assert.isTrue(penalty.gt(new BN(0)));
+		assert.isTrue(penalty.lte(new BN(500 * 1e9)));
+
+		presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.penaltyPool.toString(), penalty.toString());
+		assert.equal(presaleInfo.fractionsVesting.toString(), (1_000 * 1e9).toString());
+
+		let vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.claimedAmount.toString(), vestingInfo.totalAmount.toString());
+	});
+
+	it('Cannot exit twice', async () => {
+		let toAccount = await presale.fractionMint.createAccount(buyer.user.publicKey);
+		try {
+			await exitVestingEarly(presale, toAccount, buyer);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "There are no tokens vested in the account");
+		}
+	});
+
+	it('Remaining buyers receive the penalty when they unlock', async () => {
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		let penaltyPool = presaleInfo.penaltyPool;
+
+		await sleepUntil(vestingEnd);
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount);
+		let reward = (await getTokenAccountBalance(toAccount)).sub(new BN(1_000 * 1e9));
+		assert.equal(reward.toString(), penaltyPool.toString());
+
+		presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.penaltyPool.toString(), '0');
+		assert.equal(presaleInfo.fractionsVesting.toString(), '0');
+	});
+
+	it('Cannot exit early once vesting has finished', async () => {
+		let lateBuyer = await createBuyer(presale);
+		let toAccount = await presale.fractionMint.createAccount(lateBuyer.user.publicKey);
+		await purchaseFractions(presale, 1_000, lateBuyer);
+		try {
+			await exitVestingEarly(presale, toAccount, lateBuyer);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting period has already finished");
+		}
+	});
+
+});
@@ -0,0 +1,108 @@
+const { assert } = require('chai');
+const {
+	splToken, payerKey, program, totalFractions, getCurrentTimestamp, sleepUntil, createMint,
+	getTokenAccountBalance, setupPresale, purchaseFractions,
+} = require('./helpers');
+
+describe('fraction-presale finalization', () => {
+
+	const finalizePresale = async (presale, remainingAccounts=[]) => {
+		await program.rpc.finalizePresale(presale.pdaBump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				fractionMint: presale.fractionMint.publicKey,
+				presalePda: presale.pda,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts,
+		});
+	}
+
+	it('Cannot finalize before the presale ends', async () => {
+		let presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+		try {
+			await finalizePresale(presale);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale has not finished yet");
+		}
+	});
+
+	it('Unsold fractions are burned by default', async () => {
+		let presaleEnd = getCurrentTimestamp(10);
+		let presale = await setupPresale({ presaleEnd, vestingEnd: getCurrentTimestamp(20) });
+		await purchaseFractions(presale, 100);
+		await sleepUntil(presaleEnd);
+
+		await finalizePresale(presale);
+		let mintInfo = await presale.fractionMint.getMintInfo();
+		assert.equal(mintInfo.supply.toString(), (100 * 1e9).toString());
+		assert.equal((await getTokenAccountBalance(presale.fractionTreasury.publicKey)).toString(), "0");
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.ok(presaleInfo.finalized);
+		try {
+			await finalizePresale(presale);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale has already been finalized");
+		}
+	});
+
+	it('Unsold fractions go back to the account fixed at creation', async () => {
+		let presaleEnd = getCurrentTimestamp(10);
+		let presale = await setupPresale({ presaleEnd, vestingEnd: getCurrentTimestamp(20), returnUnsold: true });
+		await purchaseFractions(presale, 100);
+		await sleepUntil(presaleEnd);
+
+		let otherAccount = await presale.fractionMint.createAccount(payerKey);
+		try {
+			await finalizePresale(presale, [{ pubkey: otherAccount, isSigner: false, isWritable: true }]);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Unsold fractions can only be returned to the fraction account fixed when the presale was created");
+		}
+
+		await finalizePresale(presale, [{ pubkey: presale.payerFractionAccount, isSigner: false, isWritable: true }]);
+		assert.equal((await getTokenAccountBalance(presale.payerFractionAccount)).toString(), ((totalFractions - 100) * 1e9).toString());
+	});
+
+	it('The unsold destination must hold fractions', async () => {
+		let otherMint = await createMint();
+		try {
+			await setupPresale({ unsoldDestination: await otherMint.createAccount(payerKey) });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Unsold fractions can only be returned to the fraction account fixed when the presale was created");
+		}
+	});
+
+	it('Presales that never started are finalized once they end', async () => {
+		let presaleEnd = getCurrentTimestamp(10);
+		let presale = await setupPresale({ presaleEnd, vestingEnd: getCurrentTimestamp(20), returnUnsold: true, unstarted: true });
+		try {
+			await finalizePresale(presale, [{ pubkey: presale.payerFractionAccount, isSigner: false, isWritable: true }]);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale has not finished yet");
+		}
+
+		await sleepUntil(presaleEnd);
+		await finalizePresale(presale, [{ pubkey: presale.payerFractionAccount, isSigner: false, isWritable: true }]);
+		assert.equal((await getTokenAccountBalance(presale.payerFractionAccount)).toString(), (totalFractions * 1e9).toString());
+
+		try {
+			await program.rpc.startPresale({
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					operator: payerKey
+				}
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale has already been finalized");
+		}
+	});
+
+});
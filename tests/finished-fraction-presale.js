@@ -14,7 +14,7 @@ describe('fraction-presale with vesting finished', () => {
 	const program = anchor.workspace.FractionPresale;
 	anchor.setProvider(provider);
 
-	const presaleAccount = anchor.web3.Keypair.generate();
+	var presaleAccount;
 	const fractionTreasury = anchor.web3.Keypair.generate();
 	const paymentTreasury = anchor.web3.Keypair.generate();
 	const accessTreasury = anchor.web3.Keypair.generate();
@@ -22,6 +22,11 @@ describe('fraction-presale with vesting finished', () => {
 
 	var presalePDA;
 	var presalePDABump;
+	var programConfig;
+	var registry;
+	var presaleBump;
+	var presaleIndex;
+	var presaleIndexBump;
 
 	const price = 0.1;
 	const maxAmount = 1_000;
@@ -96,7 +101,7 @@ describe('fraction-presale with vesting finished', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				fromAccount: fromAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				operator: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -121,7 +126,7 @@ describe('fraction-presale with vesting finished', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				toAccount: toAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				operator: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -167,7 +172,7 @@ describe('fraction-presale with vesting finished', () => {
 			[Buffer.from("vesting"), user.publicKey.toBuffer(), presaleAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
-		await program.rpc.purchaseFractions(presalePDABump, userVestingPDABump, amount, {
+		await program.rpc.purchaseFractions(presalePDABump, userVestingPDABump, amount, 0, {
 			accounts: {
 				presaleAccount: presaleAccount.publicKey,
 				fractionTreasury: fractionTreasury.publicKey,
@@ -177,9 +182,12 @@ describe('fraction-presale with vesting finished', () => {
 				vestingAccount: vestingAccount.publicKey,
 				userVestingPda: userVestingPDA,
 				presalePda: presalePDA,
+				fractionMint: fractionMint.publicKey,
 				accessAccount: accessAccount,
 				signer: user.publicKey,
-				tokenProgram: splToken.TOKEN_PROGRAM_ID
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				rent: SYSVAR_RENT_PUBKEY,
+				systemProgram: SystemProgram.programId,
 			}
 		});
 
@@ -203,7 +211,7 @@ describe('fraction-presale with vesting finished', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				toAccount: toAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				treasurer: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -228,7 +236,7 @@ describe('fraction-presale with vesting finished', () => {
 		let vestingBalanceBefore = await getTokenAccountBalance(vestingInfo.vestingAccount);
 		console.log(vestingBalanceBefore.toString());
 		
-		await program.rpc.unlockFractions(userVestingPDABump, {
+		await program.rpc.unlockFractions(userVestingPDABump, presalePDABump, null, {
 			accounts: {
                 presaleAccount: presaleAccount.publicKey,
 				fractionTreasury: fractionTreasury.publicKey,
@@ -236,6 +244,7 @@ describe('fraction-presale with vesting finished', () => {
 				toAccount: toAccount,
 				vestingAccount: vestingAccount.publicKey,
 				userVestingPda: userVestingPDA,
+				presalePda: presalePDA,
 				signer: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID
 			},
@@ -267,6 +276,7 @@ describe('fraction-presale with vesting finished', () => {
 		assert.equal(presaleInfo.started, started);
 		assert.equal(presaleInfo.presaleEnd, presaleEnd.toString());
 		assert.equal(presaleInfo.vestingEnd, vestingEnd.toString());
+		assert.equal(presaleInfo.lotDuration.toString(), '0');
 	}
 
 	const checkVestingInfo = async (vestingKey, user=payer) => {
@@ -277,19 +287,46 @@ describe('fraction-presale with vesting finished', () => {
 		let vestingInfo = await program.account.vestingInfo.fetch(userVestingPDA);
 		assert.equal(vestingInfo.signer.toString(), user.publicKey.toString());
 		assert.equal(vestingInfo.vestingAccount.toString(), vestingKey.toString());
+		assert.equal(vestingInfo.totalAmount.sub(vestingInfo.claimedAmount).toString(), (await getTokenAccountBalance(vestingKey)).toString());
 	}
 
 	before(async () => {
+		// Presales are numbered by the registry, which the first presale of the run creates
+		let registryBump;
+		[registry, registryBump] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("registry")], program.programId);
+		if (await connection.getAccountInfo(registry) === null) {
+			await program.rpc.initRegistry(registryBump, {
+				accounts: {
+					registry: registry,
+					payer: payerKey,
+					systemProgram: SystemProgram.programId,
+				}
+			});
+		}
+		let registryInfo = await program.account.registry.fetch(registry);
+
+		fractionMint = await createMint();
+		let presaleKey;
+		[presaleKey, presaleBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("presale"), fractionMint.publicKey.toBuffer(), registryInfo.presaleCount.toArrayLike(Buffer, 'le', 8)],
+			program.programId
+		);
+		presaleAccount = { publicKey: presaleKey };
+		[presaleIndex, presaleIndexBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("presale_index"), fractionMint.publicKey.toBuffer(), new BN(0).toArrayLike(Buffer, 'le', 4)],
+			program.programId
+		);
+
 		[presalePDA, presalePDABump] = await anchor.web3.PublicKey.findProgramAddress(
 			[Buffer.from("presale"), presaleAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
+		[programConfig] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("config")], program.programId);
 	})
 
 
     it('Initialise presale account', async () => {
 
-		fractionMint = await createMint()
 		paymentMint = { publicKey: splToken.NATIVE_MINT }
 		accessMint = await createMint()
 
@@ -298,13 +335,23 @@ describe('fraction-presale with vesting finished', () => {
 
         await program.rpc.initializePresale(
 			presalePDABump,
+			presaleBump,
+			presaleIndexBump,
+			0,
 			new BN(price * 1e9),
 			new BN(maxAmount * 1e9),
 			presaleEnd,
-			vestingEnd, 
+			vestingEnd,
+			new BN(0),
+			new BN(0),
+			0,
+			new BN(0),
+			new BN(0),
 			{
 				accounts: {
 					presaleAccount: presaleAccount.publicKey,
+					registry: registry,
+					presaleIndex: presaleIndex,
 					fractionTreasury: fractionTreasury.publicKey,
 					paymentTreasury: paymentTreasury.publicKey,
 					accessTreasury: accessTreasury.publicKey,
@@ -312,12 +359,14 @@ describe('fraction-presale with vesting finished', () => {
 					fractionMint: fractionMint.publicKey,
 					paymentMint: paymentMint.publicKey,
 					accessMint: accessMint.publicKey,
+					unsoldDestination: SystemProgram.programId,
+					programConfig: programConfig,
 					authority: payer.publicKey,
 					tokenProgram: splToken.TOKEN_PROGRAM_ID,
 					rent: SYSVAR_RENT_PUBKEY,
 					systemProgram: SystemProgram.programId,
 				},
-				signers: [presaleAccount, fractionTreasury, paymentTreasury, accessTreasury]
+				signers: [fractionTreasury, paymentTreasury, accessTreasury]
 			}
 			
 		);
@@ -340,7 +389,7 @@ describe('fraction-presale with vesting finished', () => {
 		await program.rpc.startPresale({
 			accounts: {
 				presaleAccount: presaleAccount.publicKey,
-				authority: payer.publicKey
+				operator: payer.publicKey
 			}
 		});
 		startTimestamp = getCurrentTimestamp();
@@ -375,7 +424,7 @@ describe('fraction-presale with presale finished', () => {
 	const program = anchor.workspace.FractionPresale;
 	anchor.setProvider(provider);
 
-	const presaleAccount = anchor.web3.Keypair.generate();
+	var presaleAccount;
 	const fractionTreasury = anchor.web3.Keypair.generate();
 	const paymentTreasury = anchor.web3.Keypair.generate();
 	const accessTreasury = anchor.web3.Keypair.generate();
@@ -383,6 +432,11 @@ describe('fraction-presale with presale finished', () => {
 
 	var presalePDA;
 	var presalePDABump;
+	var programConfig;
+	var registry;
+	var presaleBump;
+	var presaleIndex;
+	var presaleIndexBump;
 
 	const price = 0.1;
 	const maxAmount = 1_000;
@@ -457,7 +511,7 @@ describe('fraction-presale with presale finished', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				fromAccount: fromAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				operator: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -482,7 +536,7 @@ describe('fraction-presale with presale finished', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				toAccount: toAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				operator: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -528,7 +582,7 @@ describe('fraction-presale with presale finished', () => {
 			[Buffer.from("vesting"), user.publicKey.toBuffer(), presaleAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
-		await program.rpc.purchaseFractions(presalePDABump, userVestingPDABump, amount, {
+		await program.rpc.purchaseFractions(presalePDABump, userVestingPDABump, amount, 0, {
 			accounts: {
 				presaleAccount: presaleAccount.publicKey,
 				fractionTreasury: fractionTreasury.publicKey,
@@ -538,9 +592,12 @@ describe('fraction-presale with presale finished', () => {
 				vestingAccount: vestingAccount.publicKey,
 				userVestingPda: userVestingPDA,
 				presalePda: presalePDA,
+				fractionMint: fractionMint.publicKey,
 				accessAccount: accessAccount,
 				signer: user.publicKey,
-				tokenProgram: splToken.TOKEN_PROGRAM_ID
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				rent: SYSVAR_RENT_PUBKEY,
+				systemProgram: SystemProgram.programId,
 			}
 		});
 
@@ -564,7 +621,7 @@ describe('fraction-presale with presale finished', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				toAccount: toAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				treasurer: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -589,7 +646,7 @@ describe('fraction-presale with presale finished', () => {
 		let vestingBalanceBefore = await getTokenAccountBalance(vestingInfo.vestingAccount);
 		console.log(vestingBalanceBefore.toString());
 		
-		await program.rpc.unlockFractions(userVestingPDABump, {
+		await program.rpc.unlockFractions(userVestingPDABump, presalePDABump, null, {
 			accounts: {
                 presaleAccount: presaleAccount.publicKey,
 				fractionTreasury: fractionTreasury.publicKey,
@@ -597,6 +654,7 @@ describe('fraction-presale with presale finished', () => {
 				toAccount: toAccount,
 				vestingAccount: vestingAccount.publicKey,
 				userVestingPda: userVestingPDA,
+				presalePda: presalePDA,
 				signer: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID
 			},
@@ -628,6 +686,7 @@ describe('fraction-presale with presale finished', () => {
 		assert.equal(presaleInfo.started, started);
 		assert.equal(presaleInfo.presaleEnd, presaleEnd.toString());
 		assert.equal(presaleInfo.vestingEnd, vestingEnd.toString());
+		assert.equal(presaleInfo.lotDuration.toString(), '0');
 	}
 
 	const checkVestingInfo = async (vestingKey, user=payer) => {
@@ -638,19 +697,46 @@ describe('fraction-presale with presale finished', () => {
 		let vestingInfo = await program.account.vestingInfo.fetch(userVestingPDA);
 		assert.equal(vestingInfo.signer.toString(), user.publicKey.toString());
 		assert.equal(vestingInfo.vestingAccount.toString(), vestingKey.toString());
+		assert.equal(vestingInfo.totalAmount.sub(vestingInfo.claimedAmount).toString(), (await getTokenAccountBalance(vestingKey)).toString());
 	}
 
 	before(async () => {
+		// Presales are numbered by the registry, which the first presale of the run creates
+		let registryBump;
+		[registry, registryBump] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("registry")], program.programId);
+		if (await connection.getAccountInfo(registry) === null) {
+			await program.rpc.initRegistry(registryBump, {
+				accounts: {
+					registry: registry,
+					payer: payerKey,
+					systemProgram: SystemProgram.programId,
+				}
+			});
+		}
+		let registryInfo = await program.account.registry.fetch(registry);
+
+		fractionMint = await createMint();
+		let presaleKey;
+		[presaleKey, presaleBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("presale"), fractionMint.publicKey.toBuffer(), registryInfo.presaleCount.toArrayLike(Buffer, 'le', 8)],
+			program.programId
+		);
+		presaleAccount = { publicKey: presaleKey };
+		[presaleIndex, presaleIndexBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("presale_index"), fractionMint.publicKey.toBuffer(), new BN(0).toArrayLike(Buffer, 'le', 4)],
+			program.programId
+		);
+
 		[presalePDA, presalePDABump] = await anchor.web3.PublicKey.findProgramAddress(
 			[Buffer.from("presale"), presaleAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
+		[programConfig] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("config")], program.programId);
 	})
 
 
     it('Initialise presale account', async () => {
 
-		fractionMint = await createMint()
 		paymentMint = { publicKey: splToken.NATIVE_MINT }
 		accessMint = await createMint()
 
@@ -659,13 +745,23 @@ describe('fraction-presale with presale finished', () => {
 
         await program.rpc.initializePresale(
 			presalePDABump,
+			presaleBump,
+			presaleIndexBump,
+			0,
 			new BN(price * 1e9),
 			new BN(maxAmount * 1e9),
 			presaleEnd,
-			vestingEnd, 
+			vestingEnd,
+			new BN(0),
+			new BN(0),
+			0,
+			new BN(0),
+			new BN(0),
 			{
 				accounts: {
 					presaleAccount: presaleAccount.publicKey,
+					registry: registry,
+					presaleIndex: presaleIndex,
 					fractionTreasury: fractionTreasury.publicKey,
 					paymentTreasury: paymentTreasury.publicKey,
 					accessTreasury: accessTreasury.publicKey,
@@ -673,12 +769,14 @@ describe('fraction-presale with presale finished', () => {
 					fractionMint: fractionMint.publicKey,
 					paymentMint: paymentMint.publicKey,
 					accessMint: accessMint.publicKey,
+					unsoldDestination: SystemProgram.programId,
+					programConfig: programConfig,
 					authority: payer.publicKey,
 					tokenProgram: splToken.TOKEN_PROGRAM_ID,
 					rent: SYSVAR_RENT_PUBKEY,
 					systemProgram: SystemProgram.programId,
 				},
-				signers: [presaleAccount, fractionTreasury, paymentTreasury, accessTreasury]
+				signers: [fractionTreasury, paymentTreasury, accessTreasury]
 			}
 			
 		);
@@ -701,7 +799,7 @@ describe('fraction-presale with presale finished', () => {
 		await program.rpc.startPresale({
 			accounts: {
 				presaleAccount: presaleAccount.publicKey,
-				authority: payer.publicKey
+				operator: payer.publicKey
 			}
 		});
 		startTimestamp = getCurrentTimestamp();
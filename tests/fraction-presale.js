@@ -14,7 +14,7 @@ describe('fraction-presale', () => {
 	const program = anchor.workspace.FractionPresale;
 	anchor.setProvider(provider);
 
-	const presaleAccount = anchor.web3.Keypair.generate();
+	var presaleAccount;
 	const fractionTreasury = anchor.web3.Keypair.generate();
 	const paymentTreasury = anchor.web3.Keypair.generate();
 	const accessTreasury = anchor.web3.Keypair.generate();
@@ -22,6 +22,11 @@ describe('fraction-presale', () => {
 
 	var presalePDA;
 	var presalePDABump;
+	var programConfig;
+	var registry;
+	var presaleBump;
+	var presaleIndex;
+	var presaleIndexBump;
 
 	
 	const price = 0.1;
@@ -97,7 +102,7 @@ describe('fraction-presale', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				fromAccount: fromAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				operator: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -122,7 +127,7 @@ describe('fraction-presale', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				toAccount: toAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				operator: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -168,7 +173,7 @@ describe('fraction-presale', () => {
 			[Buffer.from("vesting"), user.publicKey.toBuffer(), presaleAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
-		await program.rpc.purchaseFractions(presalePDABump, userVestingPDABump, amount, {
+		await program.rpc.purchaseFractions(presalePDABump, userVestingPDABump, amount, 0, {
 			accounts: {
 				presaleAccount: presaleAccount.publicKey,
 				fractionTreasury: fractionTreasury.publicKey,
@@ -178,9 +183,12 @@ describe('fraction-presale', () => {
 				vestingAccount: vestingAccount.publicKey,
 				userVestingPda: userVestingPDA,
 				presalePda: presalePDA,
+				fractionMint: fractionMint.publicKey,
 				accessAccount: accessAccount,
 				signer: user.publicKey,
-				tokenProgram: splToken.TOKEN_PROGRAM_ID
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				rent: SYSVAR_RENT_PUBKEY,
+				systemProgram: SystemProgram.programId,
 			}
 		});
 
@@ -204,7 +212,7 @@ describe('fraction-presale', () => {
 				paymentTreasury: paymentTreasury.publicKey,
 				toAccount: toAccount,
 				presalePda: presalePDA,
-				authority: user.publicKey,
+				treasurer: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID,
 			},
 			signers: [user]
@@ -229,7 +237,7 @@ describe('fraction-presale', () => {
 		let vestingBalanceBefore = await getTokenAccountBalance(vestingInfo.vestingAccount);
 		console.log(vestingBalanceBefore.toString());
 		
-		await program.rpc.unlockFractions(userVestingPDABump, {
+		await program.rpc.unlockFractions(userVestingPDABump, presalePDABump, null, {
 			accounts: {
                 presaleAccount: presaleAccount.publicKey,
 				fractionTreasury: fractionTreasury.publicKey,
@@ -237,6 +245,7 @@ describe('fraction-presale', () => {
 				toAccount: toAccount,
 				vestingAccount: vestingAccount.publicKey,
 				userVestingPda: userVestingPDA,
+				presalePda: presalePDA,
 				signer: user.publicKey,
 				tokenProgram: splToken.TOKEN_PROGRAM_ID
 			},
@@ -268,6 +277,7 @@ describe('fraction-presale', () => {
 		assert.equal(presaleInfo.started, started);
 		assert.equal(presaleInfo.presaleEnd, presaleEnd.toString());
 		assert.equal(presaleInfo.vestingEnd, vestingEnd.toString());
+		assert.equal(presaleInfo.lotDuration.toString(), '0');
 	}
 
 	const checkVestingInfo = async (vestingKey, user=payer) => {
@@ -278,19 +288,46 @@ describe('fraction-presale', () => {
 		let vestingInfo = await program.account.vestingInfo.fetch(userVestingPDA);
 		assert.equal(vestingInfo.signer.toString(), user.publicKey.toString());
 		assert.equal(vestingInfo.vestingAccount.toString(), vestingKey.toString());
+		assert.equal(vestingInfo.totalAmount.sub(vestingInfo.claimedAmount).toString(), (await getTokenAccountBalance(vestingKey)).toString());
 	}
 
 	before(async () => {
+		// Presales are numbered by the registry, which the first presale of the run creates
+		let registryBump;
+		[registry, registryBump] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("registry")], program.programId);
+		if (await connection.getAccountInfo(registry) === null) {
+			await program.rpc.initRegistry(registryBump, {
+				accounts: {
+					registry: registry,
+					payer: payerKey,
+					systemProgram: SystemProgram.programId,
+				}
+			});
+		}
+		let registryInfo = await program.account.registry.fetch(registry);
+
+		fractionMint = await createMint();
+		let presaleKey;
+		[presaleKey, presaleBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("presale"), fractionMint.publicKey.toBuffer(), registryInfo.presaleCount.toArrayLike(Buffer, 'le', 8)],
+			program.programId
+		);
+		presaleAccount = { publicKey: presaleKey };
+		[presaleIndex, presaleIndexBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("presale_index"), fractionMint.publicKey.toBuffer(), new BN(0).toArrayLike(Buffer, 'le', 4)],
+			program.programId
+		);
+
 		[presalePDA, presalePDABump] = await anchor.web3.PublicKey.findProgramAddress(
 			[Buffer.from("presale"), presaleAccount.publicKey.toBuffer(), program.programId.toBuffer()], 
 			program.programId
 		);
+		[programConfig] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("config")], program.programId);
 	})
 
 
     it('Initialise presale account', async () => {
 
-		fractionMint = await createMint()
 		paymentMint = { publicKey: splToken.NATIVE_MINT }
 		accessMint = await createMint()
 
@@ -299,13 +336,23 @@ describe('fraction-presale', () => {
 
         await program.rpc.initializePresale(
 			presalePDABump,
+			presaleBump,
+			presaleIndexBump,
+			0,
 			new BN(price * 1e9),
 			new BN(maxAmount * 1e9),
 			presaleEnd,
-			vestingEnd, 
+			vestingEnd,
+			new BN(0),
+			new BN(0),
+			0,
+			new BN(0),
+			new BN(0),
 			{
 				accounts: {
 					presaleAccount: presaleAccount.publicKey,
+					registry: registry,
+					presaleIndex: presaleIndex,
 					fractionTreasury: fractionTreasury.publicKey,
 					paymentTreasury: paymentTreasury.publicKey,
 					accessTreasury: accessTreasury.publicKey,
@@ -313,12 +360,14 @@ describe('fraction-presale', () => {
 					fractionMint: fractionMint.publicKey,
 					paymentMint: paymentMint.publicKey,
 					accessMint: accessMint.publicKey,
+					unsoldDestination: SystemProgram.programId,
+					programConfig: programConfig,
 					authority: payer.publicKey,
 					tokenProgram: splToken.TOKEN_PROGRAM_ID,
 					rent: SYSVAR_RENT_PUBKEY,
 					systemProgram: SystemProgram.programId,
 				},
-				signers: [presaleAccount, fractionTreasury, paymentTreasury, accessTreasury]
+				signers: [fractionTreasury, paymentTreasury, accessTreasury]
 			}
 			
 		);
@@ -395,7 +444,7 @@ describe('fraction-presale', () => {
 			await program.rpc.startPresale({
 				accounts: {
 					presaleAccount: presaleAccount.publicKey,
-					authority: badActor.publicKey
+					operator: badActor.publicKey
 				},
 				signers: [badActor]
 			});
@@ -409,7 +458,7 @@ describe('fraction-presale', () => {
 		await program.rpc.startPresale({
 			accounts: {
 				presaleAccount: presaleAccount.publicKey,
-				authority: payer.publicKey
+				operator: payer.publicKey
 			}
 		});
 		startTimestamp = getCurrentTimestamp();
@@ -421,7 +470,7 @@ describe('fraction-presale', () => {
 			await program.rpc.startPresale({
 				accounts: {
 					presaleAccount: presaleAccount.publicKey,
-					authority: payer.publicKey
+					operator: payer.publicKey
 				}
 			});
 			assert.ok(false);
@@ -0,0 +1,111 @@
+const { assert } = require('chai');
+const {
+	splToken, BN, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance,
+	createBuyer, setupPresale, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with revocable grants', () => {
+
+	var presale;
+	var teamMember;
+	var tgeTimestamp;
+
+	const grantFractions = async (amount, revocable) => {
+		await program.rpc.grantFractions(presale.pdaBump, teamMember.vestingPDABump, new BN(amount * 1e9), revocable, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				vestingAccount: teamMember.vestingAccount.publicKey,
+				userVestingPda: teamMember.vestingPDA,
+				presalePda: presale.pda,
+				operator: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts: teamMember.remainingAccounts,
+		});
+	}
+
+	const revokeGrant = async () => {
+		await program.rpc.revokeGrant(teamMember.vestingPDABump, presale.pdaBump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				vestingAccount: teamMember.vestingAccount.publicKey,
+				userVestingPda: teamMember.vestingPDA,
+				presalePda: presale.pda,
+				operator: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			}
+		});
+	}
+
+	it('Initialise presale with a TGE unlock', async () => {
+		tgeTimestamp = getCurrentTimestamp(15);
+		presale = await setupPresale({ tgeTimestamp, vestingEnd: tgeTimestamp + 1000, tgeUnlockBps: 2_500 });
+		teamMember = await createBuyer(presale);
+	});
+
+	it('Grant fractions without payment or an access token', async () => {
+		let treasuryBefore = await getTokenAccountBalance(presale.fractionTreasury.publicKey);
+		await grantFractions(1_000, true);
+		await grantFractions(500, false);
+
+		let vestingInfo = await program.account.vestingInfo.fetch(teamMember.vestingPDA);
+		assert.equal(vestingInfo.totalAmount.toString(), (1_500 * 1e9).toString());
+		assert.equal(vestingInfo.revocableAmount.toString(), (1_000 * 1e9).toString());
+		let treasuryAfter = await getTokenAccountBalance(presale.fractionTreasury.publicKey);
+		assert.equal(treasuryBefore.sub(treasuryAfter).toString(), (1_500 * 1e9).toString());
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.fractionsSold.toString(), '0');
+	});
+
+	it('Only the operator can grant fractions', async () => {
+		try {
+			await program.rpc.grantFractions(presale.pdaBump, teamMember.vestingPDABump, new BN(1e9), false, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					fractionTreasury: presale.fractionTreasury.publicKey,
+					vestingAccount: teamMember.vestingAccount.publicKey,
+					userVestingPda: teamMember.vestingPDA,
+					presalePda: presale.pda,
+					operator: teamMember.user.publicKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				},
+				signers: [teamMember.user]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+	});
+
+	it('Revoking returns the unvested part of revocable grants', async () => {
+		await sleepUntil(tgeTimestamp);
+		let treasuryBefore = await getTokenAccountBalance(presale.fractionTreasury.publicKey);
+		await revokeGrant();
+
+		let treasuryAfter = await getTokenAccountBalance(presale.fractionTreasury.publicKey);
+		assert.equal(treasuryAfter.sub(treasuryBefore).toString(), (750 * 1e9).toString());
+
+		let vestingInfo = await program.account.vestingInfo.fetch(teamMember.vestingPDA);
+		assert.equal(vestingInfo.totalAmount.toString(), (750 * 1e9).toString());
+		assert.equal(vestingInfo.revocableAmount.toString(), '0');
+		assert.equal(vestingInfo.vestedFloor.toString(), (250 * 1e9).toString());
+	});
+
+	it('The vested part stays claimable', async () => {
+		// The 250 already vested out of the revoked grant, and the TGE share of the other 500
+		let toAccount = await presale.fractionMint.createAccount(teamMember.user.publicKey);
+		await unlockFractions(presale, toAccount, teamMember);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (375 * 1e9).toString());
+
+		try {
+			await revokeGrant();
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "There are no unvested revocable fractions left to revoke");
+		}
+	});
+
+});
@@ -0,0 +1,404 @@
+const anchor = require('@project-serum/anchor');
+const splToken = require('@solana/spl-token');
+const BN = require('BN.js');
+const { SystemProgram, SYSVAR_RENT_PUBKEY, Keypair, Transaction } = anchor.web3;
+
+const provider = anchor.Provider.env();
+const connection = provider.connection;
+const payer = provider.wallet.payer;
+const payerKey = payer.publicKey;
+const program = anchor.workspace.FractionPresale;
+anchor.setProvider(provider);
+
+const price = 0.1;
+const maxAmount = 1_000;
+const totalFractions = 1_000_000;
+const DECIMALS = 9;
+
+const getCurrentTimestamp = (n=0) => {
+	return n + Math.floor(Date.now() / 1000);
+}
+
+const sleep = (seconds) => {
+	return new Promise(resolve => setTimeout(resolve, seconds * 1000));
+}
+
+const sleepUntil = async (timestamp) => {
+	await sleep(Math.max(timestamp - getCurrentTimestamp() + 1, 0));
+}
+
+const createNativeTokenAccount = async (amount, user=payer) => {
+	let balanceNeeded = await splToken.Token.getMinBalanceRentForExemptAccount(connection);
+	let newAccount = Keypair.generate();
+	let transaction = new Transaction();
+	transaction.add(
+		SystemProgram.createAccount({
+			fromPubkey: payer.publicKey,
+			newAccountPubkey: newAccount.publicKey,
+			lamports: balanceNeeded + (amount * 1e9),
+			space: splToken.AccountLayout.span,
+			programId: splToken.TOKEN_PROGRAM_ID,
+		}),
+		splToken.Token.createInitAccountInstruction(
+			splToken.TOKEN_PROGRAM_ID,
+			splToken.NATIVE_MINT,
+			newAccount.publicKey,
+			user.publicKey,
+		)
+	);
+	await provider.send(transaction, [payer, newAccount]);
+	return newAccount.publicKey;
+}
+
+const createMint = async (authority=payerKey) => {
+	return await splToken.Token.createMint(
+		provider.connection, payer,
+		authority, // mint authority
+		authority, // freeze authority
+		DECIMALS, splToken.TOKEN_PROGRAM_ID,
+	);
+}
+
+const getTokenAccountBalance = async (address) => {
+	let res = await connection.getTokenAccountBalance(address);
+	return new BN(res.value.amount);
+}
+
+// Derives the addresses of a vesting position for `user` without opening it.
+// Pooled positions all share the presale's escrow, so there is no token account to create
+const derivePosition = async (presale, user) => {
+	const position = {
+		user,
+		vestingAccount: presale.vestingEscrow ? { publicKey: presale.vestingEscrow } : Keypair.generate(),
+		remainingAccounts: [],
+		opened: !!presale.vestingEscrow,
+	};
+
+	[position.vestingPDA, position.vestingPDABump] = await anchor.web3.PublicKey.findProgramAddress(
+		[Buffer.from("vesting"), user.publicKey.toBuffer(), presale.account.publicKey.toBuffer(), program.programId.toBuffer()],
+		program.programId
+	);
+	[position.lotsPDA, position.lotsPDABump] = await anchor.web3.PublicKey.findProgramAddress(
+		[Buffer.from("lots"), user.publicKey.toBuffer(), presale.account.publicKey.toBuffer(), program.programId.toBuffer()],
+		program.programId
+	);
+	// Lots are opened by `initVestingLots`, or otherwise by the first purchase
+	if (presale.schedule.lotDuration) {
+		position.remainingAccounts.push({ pubkey: position.lotsPDA, isWritable: true, isSigner: false });
+	}
+	return position;
+}
+
+// Opens a vesting account for `user`, along with its lots when the presale uses them
+const openPosition = async (presale, user=payer) => {
+	const position = await derivePosition(presale, user);
+	if (presale.vestingEscrow) {
+		// Pooled positions are opened by the first purchase
+		return position;
+	}
+
+	await program.rpc.initVestingAccount(position.vestingPDABump, {
+		accounts: {
+			presaleAccount: presale.account.publicKey,
+			fractionTreasury: presale.fractionTreasury.publicKey,
+			paymentTreasury: presale.paymentTreasury.publicKey,
+			vestingAccount: position.vestingAccount.publicKey,
+			userVestingPda: position.vestingPDA,
+			fractionMint: presale.fractionMint.publicKey,
+			signer: user.publicKey,
+			tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			rent: SYSVAR_RENT_PUBKEY,
+			systemProgram: SystemProgram.programId,
+		},
+		signers: [position.vestingAccount, user]
+	});
+	position.opened = true;
+
+	if (presale.schedule.lotDuration) {
+		await program.rpc.initVestingLots(position.vestingPDABump, position.lotsPDABump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				userVestingPda: position.vestingPDA,
+				vestingLots: position.lotsPDA,
+				signer: user.publicKey,
+				systemProgram: SystemProgram.programId,
+			},
+			signers: [user]
+		});
+	}
+
+	return position;
+}
+
+// Creates a funded buyer with their own vesting account, or leaves it for their first purchase to open
+const createBuyer = async (presale, open=true) => {
+	let user = Keypair.generate();
+	await connection.confirmTransaction(await connection.requestAirdrop(user.publicKey, 10 * 1e9));
+	return open ? await openPosition(presale, user) : await derivePosition(presale, user);
+}
+
+// Presales are numbered by the registry, which the first presale of the run creates
+const getRegistry = async () => {
+	let [registry, registryBump] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("registry")], program.programId);
+	if (await connection.getAccountInfo(registry) === null) {
+		await program.rpc.initRegistry(registryBump, {
+			accounts: {
+				registry,
+				payer: payerKey,
+				systemProgram: SystemProgram.programId,
+			}
+		});
+	}
+	return registry;
+}
+
+// Creates, funds and starts a presale with the given vesting schedule.
+// The payer's vesting account is opened and merged into the returned presale.
+// Presales can share a `fractionMint`, indexed into `presaleIndexPage` once the pages before it are full
+const setupPresale = async (schedule) => {
+	const presale = {
+		schedule,
+		fractionTreasury: Keypair.generate(),
+		paymentTreasury: Keypair.generate(),
+		accessTreasury: Keypair.generate(),
+	};
+
+	presale.fractionMint = schedule.fractionMint || await createMint();
+	let registry = await getRegistry();
+	let registryInfo = await program.account.registry.fetch(registry);
+	let [presaleKey, presaleBump] = await anchor.web3.PublicKey.findProgramAddress(
+		[Buffer.from("presale"), presale.fractionMint.publicKey.toBuffer(), registryInfo.presaleCount.toArrayLike(Buffer, 'le', 8)],
+		program.programId
+	);
+	presale.account = { publicKey: presaleKey };
+	const presaleIndexPage = schedule.presaleIndexPage || 0;
+	const findPresaleIndex = (page) => anchor.web3.PublicKey.findProgramAddress(
+		[Buffer.from("presale_index"), presale.fractionMint.publicKey.toBuffer(), new BN(page).toArrayLike(Buffer, 'le', 4)],
+		program.programId
+	);
+	let presaleIndexBump;
+	[presale.presaleIndex, presaleIndexBump] = await findPresaleIndex(presaleIndexPage);
+	let remainingAccounts = [];
+	if (presaleIndexPage > 0) {
+		let [previousPage] = await findPresaleIndex(presaleIndexPage - 1);
+		remainingAccounts.push({ pubkey: previousPage, isWritable: false, isSigner: false });
+	}
+
+	[presale.pda, presale.pdaBump] = await anchor.web3.PublicKey.findProgramAddress(
+		[Buffer.from("presale"), presale.account.publicKey.toBuffer(), program.programId.toBuffer()],
+		program.programId
+	);
+
+	presale.accessMint = await createMint();
+	let payerFractionAccount = await presale.fractionMint.createAccount(payerKey);
+	await presale.fractionMint.mintTo(payerFractionAccount, payerKey, [], totalFractions * 1e9);
+	presale.payerFractionAccount = payerFractionAccount;
+
+	await program.rpc.initializePresale(
+		presale.pdaBump,
+		presaleBump,
+		presaleIndexBump,
+		presaleIndexPage,
+		new BN(price * 1e9),
+		new BN(maxAmount * 1e9),
+		new BN(schedule.presaleEnd || getCurrentTimestamp(1000)),
+		new BN(schedule.vestingStartDeadline ? 0 : (schedule.vestingEnd || getCurrentTimestamp(1000))),
+		new BN(schedule.lotDuration || 0),
+		new BN(schedule.tgeTimestamp || 0),
+		schedule.tgeUnlockBps || 0,
+		new BN(schedule.vestingStartDeadline || 0),
+		new BN(schedule.vestingDuration || 0),
+		{
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				registry,
+				presaleIndex: presale.presaleIndex,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				paymentTreasury: presale.paymentTreasury.publicKey,
+				accessTreasury: presale.accessTreasury.publicKey,
+				presalePda: presale.pda,
+				fractionMint: presale.fractionMint.publicKey,
+				paymentMint: splToken.NATIVE_MINT,
+				accessMint: presale.accessMint.publicKey,
+				unsoldDestination: schedule.unsoldDestination || (schedule.returnUnsold ? payerFractionAccount : SystemProgram.programId),
+				programConfig: (await anchor.web3.PublicKey.findProgramAddress([Buffer.from("config")], program.programId))[0],
+				authority: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				rent: SYSVAR_RENT_PUBKEY,
+				systemProgram: SystemProgram.programId,
+			},
+			remainingAccounts,
+			signers: [presale.fractionTreasury, presale.paymentTreasury, presale.accessTreasury]
+		}
+	);
+
+	await program.rpc.addFractionsForSale(presale.pdaBump, new BN(totalFractions * 1e9), {
+		accounts: {
+			presaleAccount: presale.account.publicKey,
+			fractionTreasury: presale.fractionTreasury.publicKey,
+			paymentTreasury: presale.paymentTreasury.publicKey,
+			fromAccount: payerFractionAccount,
+			presalePda: presale.pda,
+			operator: payerKey,
+			tokenProgram: splToken.TOKEN_PROGRAM_ID,
+		}
+	});
+
+	if (schedule.milestones) {
+		await program.rpc.setMilestones(schedule.milestones, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey
+			}
+		});
+	}
+
+	if (schedule.pooled) {
+		let escrowBump;
+		[presale.vestingEscrow, escrowBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("escrow"), presale.account.publicKey.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+		await program.rpc.initVestingEscrow(presale.pdaBump, escrowBump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				vestingEscrow: presale.vestingEscrow,
+				presalePda: presale.pda,
+				fractionMint: presale.fractionMint.publicKey,
+				authority: payerKey,
+				payer: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				rent: SYSVAR_RENT_PUBKEY,
+				systemProgram: SystemProgram.programId,
+			}
+		});
+	}
+
+	if (schedule.lockupOptions) {
+		await program.rpc.setLockupOptions(schedule.lockupOptions, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey
+			}
+		});
+	}
+
+	if (schedule.vestingReceipts) {
+		await program.rpc.setTransferPolicy(false, true, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey
+			}
+		});
+	}
+
+	if (schedule.earlyExitPenaltyBps) {
+		await program.rpc.configureEarlyExit(schedule.earlyExitPenaltyBps, !!schedule.penaltyToPool, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey
+			}
+		});
+	}
+
+	// Presales set up as `unstarted` are left for the test to start
+	if (schedule.unstarted) {
+		return presale;
+	}
+
+	await program.rpc.startPresale({
+		accounts: {
+			presaleAccount: presale.account.publicKey,
+			operator: payerKey
+		}
+	});
+
+	return Object.assign(presale, await openPosition(presale));
+}
+
+// The purchase that mints a position's receipt is passed a new token account to hold it
+const purchaseFractions = async (presale, amount, position=presale, lockupOption=0, receiptAccount=null) => {
+	let remainingAccounts = position.remainingAccounts;
+	let signers = position.opened ? [position.user] : [position.user, position.vestingAccount];
+	if (receiptAccount !== null) {
+		let [receiptMint] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("receipt"), position.vestingPDA.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+		remainingAccounts = remainingAccounts.concat([
+			{ pubkey: receiptMint, isWritable: true, isSigner: false },
+			{ pubkey: receiptAccount.publicKey, isWritable: true, isSigner: true },
+		]);
+		signers.push(receiptAccount);
+	}
+
+	let accessAccount = await presale.accessMint.createAccount(position.user.publicKey);
+	await presale.accessMint.mintTo(accessAccount, payerKey, [], 1);
+	let paymentAccount = await createNativeTokenAccount(amount * price, position.user);
+
+	await program.rpc.purchaseFractions(presale.pdaBump, position.vestingPDABump, new BN((amount * 10**DECIMALS).toString()), lockupOption, {
+		accounts: {
+			presaleAccount: presale.account.publicKey,
+			fractionTreasury: presale.fractionTreasury.publicKey,
+			paymentTreasury: presale.paymentTreasury.publicKey,
+			accessTreasury: presale.accessTreasury.publicKey,
+			fromAccount: paymentAccount,
+			vestingAccount: position.vestingAccount.publicKey,
+			userVestingPda: position.vestingPDA,
+			presalePda: presale.pda,
+			fractionMint: presale.fractionMint.publicKey,
+			accessAccount: accessAccount,
+			signer: position.user.publicKey,
+			tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			rent: SYSVAR_RENT_PUBKEY,
+			systemProgram: SystemProgram.programId,
+		},
+		remainingAccounts,
+		signers
+	});
+	position.opened = true;
+}
+
+// Unlocks everything that has vested unless an amount is given
+const unlockFractions = async (presale, toAccount, position=presale, amount=null) => {
+	let unlockAmount = amount === null ? null : new BN((amount * 10**DECIMALS).toString());
+	await program.rpc.unlockFractions(position.vestingPDABump, presale.pdaBump, unlockAmount, {
+		accounts: {
+			presaleAccount: presale.account.publicKey,
+			fractionTreasury: presale.fractionTreasury.publicKey,
+			paymentTreasury: presale.paymentTreasury.publicKey,
+			toAccount: toAccount,
+			vestingAccount: position.vestingAccount.publicKey,
+			userVestingPda: position.vestingPDA,
+			presalePda: presale.pda,
+			signer: position.user.publicKey,
+			tokenProgram: splToken.TOKEN_PROGRAM_ID
+		},
+		remainingAccounts: position.remainingAccounts,
+		signers: [position.user]
+	});
+}
+
+const exitVestingEarly = async (presale, toAccount, position=presale) => {
+	await program.rpc.exitVestingEarly(position.vestingPDABump, presale.pdaBump, {
+		accounts: {
+			presaleAccount: presale.account.publicKey,
+			fractionTreasury: presale.fractionTreasury.publicKey,
+			toAccount: toAccount,
+			vestingAccount: position.vestingAccount.publicKey,
+			userVestingPda: position.vestingPDA,
+			presalePda: presale.pda,
+			signer: position.user.publicKey,
+			tokenProgram: splToken.TOKEN_PROGRAM_ID
+		},
+		signers: [position.user]
+	});
+}
+
+module.exports = {
+	anchor, splToken, BN, SystemProgram, SYSVAR_RENT_PUBKEY, Keypair, Transaction, provider,
+	connection, payer, payerKey, program, price, maxAmount, totalFractions, DECIMALS,
+	getCurrentTimestamp, sleep, sleepUntil, createNativeTokenAccount, createMint,
+	getTokenAccountBalance, derivePosition, openPosition, createBuyer, getRegistry, setupPresale,
+	purchaseFractions, unlockFractions, exitVestingEarly,
+};
@@ -0,0 +1,131 @@
+const { assert } = require('chai');
+const {
+	splToken, BN, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance,
+	createBuyer, setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with lockup options', () => {
+
+	const lockupOptions = [
+		{ multiplierBps: 10_000, bonusBps: 0 },
+		{ multiplierBps: 20_000, bonusBps: 1_000 },
+	];
+	var presale;
+	var buyer;
+	var grantee;
+	var presaleEnd;
+	var vestingEnd;
+	var lockupEnd;
+
+	it('Lockups need a vesting period after the sale', async () => {
+		let presaleEnd = getCurrentTimestamp(15);
+		try {
+			await setupPresale({ presaleEnd, vestingEnd: presaleEnd, lockupOptions });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting schedule parameters are invalid");
+		}
+	});
+
+	it('Initialise presale with lockup options', async () => {
+		presaleEnd = getCurrentTimestamp(15);
+		vestingEnd = presaleEnd + 5;
+		lockupEnd = presaleEnd + 10;
+		presale = await setupPresale({ presaleEnd, vestingEnd, lockupOptions });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.lockupOptions.length, 2);
+		assert.equal(presaleInfo.lockupOptions[1].multiplierBps, 20_000);
+		assert.equal(presaleInfo.lockupOptions[1].bonusBps, 1_000);
+		buyer = await createBuyer(presale);
+		grantee = await createBuyer(presale);
+	});
+
+	it('Presale dates are frozen once lockups are offered', async () => {
+		try {
+			await program.rpc.updatePresaleParams(null, null, new BN(presaleEnd + 5), null, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					authority: payerKey,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting schedule parameters are invalid");
+		}
+	});
+
+	it('Longer lockups receive bonus fractions', async () => {
+		await purchaseFractions(presale, 1_000, presale, 1);
+		await purchaseFractions(presale, 1_000, buyer, 0);
+
+		let vestingInfo = await program.account.vestingInfo.fetch(presale.vestingPDA);
+		assert.equal(vestingInfo.lockupMultiplierBps, 20_000);
+		assert.equal(vestingInfo.totalAmount.toString(), (1_100 * 1e9).toString());
+		assert.equal((await getTokenAccountBalance(presale.vestingAccount.publicKey)).toString(), (1_100 * 1e9).toString());
+
+		vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.lockupMultiplierBps, 10_000);
+		assert.equal(vestingInfo.totalAmount.toString(), (1_000 * 1e9).toString());
+	});
+
+	it('Cannot switch lockup on a later purchase', async () => {
+		try {
+			await purchaseFractions(presale, 1_000, presale, 0);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Every purchase must use the lockup chosen on the first one");
+		}
+	});
+
+	it('Cannot choose a lockup that is not offered', async () => {
+		try {
+			await purchaseFractions(presale, 1_000, buyer, 2);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale does not offer this lockup option");
+		}
+	});
+
+	it('A granted position takes the lockup of its first purchase', async () => {
+		await program.rpc.grantFractions(presale.pdaBump, grantee.vestingPDABump, new BN(500 * 1e9), false, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				vestingAccount: grantee.vestingAccount.publicKey,
+				userVestingPda: grantee.vestingPDA,
+				presalePda: presale.pda,
+				operator: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts: grantee.remainingAccounts,
+		});
+		let vestingInfo = await program.account.vestingInfo.fetch(grantee.vestingPDA);
+		assert.equal(vestingInfo.lockupMultiplierBps, 0);
+
+		await purchaseFractions(presale, 1_000, grantee, 1);
+		vestingInfo = await program.account.vestingInfo.fetch(grantee.vestingPDA);
+		assert.equal(vestingInfo.lockupMultiplierBps, 20_000);
+		assert.equal(vestingInfo.totalAmount.toString(), (1_600 * 1e9).toString());
+	});
+
+	it('The longer lockup is enforced at unlock', async () => {
+		await sleepUntil(vestingEnd);
+
+		let toAccount = await presale.fractionMint.createAccount(buyer.user.publicKey);
+		await unlockFractions(presale, toAccount, buyer);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_000 * 1e9).toString());
+
+		toAccount = await presale.fractionMint.createAccount(payerKey);
+		try {
+			await unlockFractions(presale, toAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting period has not finished");
+		}
+
+		await sleepUntil(lockupEnd);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_100 * 1e9).toString());
+	});
+
+});
@@ -0,0 +1,86 @@
+const { assert } = require('chai');
+const {
+	payerKey, program, getCurrentTimestamp, sleep, sleepUntil, getTokenAccountBalance, createBuyer,
+	setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with vesting lots', () => {
+
+	const lotDuration = 5;
+	var presale;
+
+	it('Initialise presale with vesting lots', async () => {
+		presale = await setupPresale({ lotDuration });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.lotDuration.toString(), lotDuration.toString());
+
+		let vestingLots = await program.account.vestingLots.fetch(presale.lotsPDA);
+		assert.equal(vestingLots.vestingInfo.toString(), presale.vestingPDA.toString());
+		assert.equal(vestingLots.lots.length, 0);
+	});
+
+	it('Each purchase opens its own lot', async () => {
+		let before = getCurrentTimestamp();
+		await purchaseFractions(presale, 100);
+		await sleep(3);
+		await purchaseFractions(presale, 200);
+
+		let vestingLots = await program.account.vestingLots.fetch(presale.lotsPDA);
+		assert.equal(vestingLots.lots.length, 2);
+		assert.equal(vestingLots.lots[0].amount.toString(), (100 * 1e9).toString());
+		assert.equal(vestingLots.lots[1].amount.toString(), (200 * 1e9).toString());
+		assert.closeTo(vestingLots.lots[0].purchaseTime.toNumber(), before, 2);
+		assert.equal(vestingLots.lots[0].unlockTime.sub(vestingLots.lots[0].purchaseTime).toString(), lotDuration.toString());
+	});
+
+	it('The first purchase opens the lots of a new position', async () => {
+		let buyer = await createBuyer(presale, false);
+		await purchaseFractions(presale, 100, buyer);
+
+		let vestingLots = await program.account.vestingLots.fetch(buyer.lotsPDA);
+		assert.equal(vestingLots.vestingInfo.toString(), buyer.vestingPDA.toString());
+		assert.equal(vestingLots.lots.length, 1);
+		assert.equal(vestingLots.lots[0].amount.toString(), (100 * 1e9).toString());
+	});
+
+	it('Cannot unlock before any lot has matured', async () => {
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		try {
+			await unlockFractions(presale, toAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting period has not finished");
+		}
+	});
+
+	it('Unlock only releases matured lots', async () => {
+		let vestingLots = await program.account.vestingLots.fetch(presale.lotsPDA);
+		await sleepUntil(vestingLots.lots[0].unlockTime.toNumber());
+
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount);
+
+		let toBalance = await getTokenAccountBalance(toAccount);
+		let vestingBalance = await getTokenAccountBalance(presale.vestingAccount.publicKey);
+		vestingLots = await program.account.vestingLots.fetch(presale.lotsPDA);
+		assert.equal(toBalance.toString(), (100 * 1e9).toString());
+		assert.equal(vestingBalance.toString(), (200 * 1e9).toString());
+		assert.equal(vestingLots.lots.length, 1);
+	});
+
+	it('Matured lots are merged to make room for new ones', async () => {
+		let vestingLots = await program.account.vestingLots.fetch(presale.lotsPDA);
+		while (vestingLots.lots.length < 16) {
+			await purchaseFractions(presale, 10);
+			vestingLots = await program.account.vestingLots.fetch(presale.lotsPDA);
+		}
+		await sleepUntil(vestingLots.lots[15].unlockTime.toNumber());
+
+		await purchaseFractions(presale, 10);
+		vestingLots = await program.account.vestingLots.fetch(presale.lotsPDA);
+		assert.equal(vestingLots.lots.length, 2);
+		assert.equal(vestingLots.lots[0].amount.toString(), (350 * 1e9).toString());
+		assert.equal(vestingLots.lots[1].amount.toString(), (10 * 1e9).toString());
+	});
+
+});
@@ -0,0 +1,132 @@
+const { assert } = require('chai');
+const {
+	BN, Keypair, payer, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance,
+	setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with milestone vesting', () => {
+
+	var presale;
+	var toAccount;
+	var presaleEnd;
+	var firstDeadline;
+	var lastDeadline;
+
+	const milestone = (unlockBps, deadline) => ({ unlockBps, deadline: new BN(deadline), reached: false });
+
+	const markMilestoneReached = async (index, user=payer) => {
+		await program.rpc.markMilestoneReached(index, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: user.publicKey
+			},
+			signers: [user]
+		});
+	}
+
+	it('Milestone deadlines must follow the presale end in order', async () => {
+		let end = getCurrentTimestamp(1000);
+		try {
+			await setupPresale({ presaleEnd: end, milestones: [milestone(5_000, end), milestone(5_000, end + 10)] });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The milestones are invalid");
+		}
+		try {
+			await setupPresale({ presaleEnd: end, milestones: [milestone(5_000, end + 20), milestone(5_000, end + 10)] });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The milestones are invalid");
+		}
+	});
+
+	it('Initialise presale with milestones', async () => {
+		presaleEnd = getCurrentTimestamp(20);
+		firstDeadline = presaleEnd + 10;
+		lastDeadline = presaleEnd + 1000;
+		presale = await setupPresale({
+			presaleEnd,
+			vestingEnd: lastDeadline,
+			milestones: [
+				milestone(5_000, firstDeadline),
+				milestone(3_000, lastDeadline),
+				milestone(2_000, lastDeadline),
+			]
+		});
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.milestones.length, 3);
+		await purchaseFractions(presale, 1_000);
+		toAccount = await presale.fractionMint.createAccount(payerKey);
+	});
+
+	it('Cannot set milestones once the presale has started', async () => {
+		try {
+			await program.rpc.setMilestones([milestone(10_000, lastDeadline)], {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					authority: payerKey
+				}
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale cannot be started more than once");
+		}
+	});
+
+	it('The sale cannot be extended past a milestone deadline', async () => {
+		try {
+			await program.rpc.updatePresaleParams(null, null, new BN(firstDeadline), null, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					authority: payerKey,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The milestones are invalid");
+		}
+	});
+
+	it('Cannot unlock before any milestone is reached', async () => {
+		try {
+			await unlockFractions(presale, toAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting period has not finished");
+		}
+	});
+
+	it('Only authority can mark milestones', async () => {
+		let badActor = Keypair.generate();
+		try {
+			await markMilestoneReached(0, badActor);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+	});
+
+	it('Reached milestones unlock their share', async () => {
+		await markMilestoneReached(1);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (300 * 1e9).toString());
+
+		try {
+			await markMilestoneReached(1);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The milestone has already been reached");
+		}
+	});
+
+	it('Milestones unlock automatically after their deadline', async () => {
+		await sleepUntil(firstDeadline);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (800 * 1e9).toString());
+
+		await markMilestoneReached(2);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_000 * 1e9).toString());
+	});
+
+});
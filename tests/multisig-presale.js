@@ -0,0 +1,179 @@
+const { assert } = require('chai');
+const {
+	anchor, SystemProgram, Keypair, connection, payerKey, program, getCurrentTimestamp,
+	setupPresale,
+} = require('./helpers');
+
+describe('fraction-presale governed by a multisig', () => {
+
+	var presale;
+	var multisigPDA, multisigBump;
+	const owners = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+	const newAuthority = Keypair.generate();
+
+	const proposeTransaction = async (ixName, args, accounts, proposer) => {
+		let transaction = Keypair.generate();
+		let metas = program.instruction[ixName].accounts(accounts).map(meta => ({
+			pubkey: meta.pubkey,
+			isSigner: false,
+			isWritable: meta.isWritable,
+		}));
+		await program.rpc.proposeTransaction(program.programId, metas, program.coder.instruction.encode(ixName, args), {
+			accounts: {
+				multisig: multisigPDA,
+				transaction: transaction.publicKey,
+				proposer: proposer.publicKey,
+				systemProgram: SystemProgram.programId,
+			},
+			signers: [transaction, proposer]
+		});
+		return { transaction: transaction.publicKey, metas, proposer: proposer.publicKey };
+	}
+
+	const executeTransaction = async ({ transaction, metas, proposer }, executor) => {
+		await program.rpc.executeTransaction(multisigBump, {
+			accounts: {
+				multisig: multisigPDA,
+				transaction,
+				proposer,
+				executor: executor.publicKey,
+			},
+			remainingAccounts: metas.concat({ pubkey: program.programId, isSigner: false, isWritable: false }),
+			signers: [executor]
+		});
+	}
+
+	it('Initialise presale', async () => {
+		presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+		for (let owner of owners) {
+			await connection.confirmTransaction(await connection.requestAirdrop(owner.publicKey, 1e9));
+		}
+	});
+
+	it('Rejects a threshold above the number of owners', async () => {
+		[multisigPDA, multisigBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("multisig"), presale.account.publicKey.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+		try {
+			await program.rpc.createMultisig(multisigBump, owners.map(owner => owner.publicKey), 4, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					multisig: multisigPDA,
+					authority: payerKey,
+					systemProgram: SystemProgram.programId,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The multisig threshold must be between one and the number of owners");
+		}
+	});
+
+	it('Multisig takes over as the presale authority', async () => {
+		await program.rpc.createMultisig(multisigBump, owners.map(owner => owner.publicKey), 2, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				multisig: multisigPDA,
+				authority: payerKey,
+				systemProgram: SystemProgram.programId,
+			},
+		});
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.authority.toString(), multisigPDA.toString());
+		assert.equal(presaleInfo.operator.toString(), multisigPDA.toString());
+		assert.equal(presaleInfo.treasurer.toString(), multisigPDA.toString());
+		assert.equal(presaleInfo.pauser.toString(), multisigPDA.toString());
+
+		try {
+			await program.rpc.proposeAuthority(payerKey, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					authority: payerKey,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+	});
+
+	it('Transactions only execute once enough owners approve', async () => {
+		let proposal = await proposeTransaction('propose_authority', { newAuthority: newAuthority.publicKey }, {
+			presaleAccount: presale.account.publicKey,
+			authority: multisigPDA,
+		}, owners[0]);
+
+		try {
+			await executeTransaction(proposal, owners[0]);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The transaction does not have enough approvals");
+		}
+
+		await program.rpc.approveTransaction({
+			accounts: {
+				multisig: multisigPDA,
+				transaction: proposal.transaction,
+				owner: owners[1].publicKey,
+			},
+			signers: [owners[1]]
+		});
+		let proposerBalance = await connection.getBalance(owners[0].publicKey);
+		await executeTransaction(proposal, owners[1]);
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.pendingAuthority.toString(), newAuthority.publicKey.toString());
+
+		// Executed transactions are closed and their rent refunded to the proposer
+		assert.equal(await connection.getAccountInfo(proposal.transaction), null);
+		assert.isTrue((await connection.getBalance(owners[0].publicKey)) > proposerBalance);
+	});
+
+	it('Only owners can propose transactions', async () => {
+		let outsider = Keypair.generate();
+		await connection.confirmTransaction(await connection.requestAirdrop(outsider.publicKey, 1e9));
+		try {
+			await proposeTransaction('propose_authority', { newAuthority: outsider.publicKey }, {
+				presaleAccount: presale.account.publicKey,
+				authority: multisigPDA,
+			}, outsider);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The signer is not an owner of the multisig");
+		}
+	});
+
+	it('Changing the owners voids pending approvals', async () => {
+		let pending = await proposeTransaction('propose_authority', { newAuthority: payerKey }, {
+			presaleAccount: presale.account.publicKey,
+			authority: multisigPDA,
+		}, owners[0]);
+
+		let rotation = await proposeTransaction('set_multisig_owners', { owners: [owners[0].publicKey, owners[1].publicKey], threshold: 1 }, {
+			multisig: multisigPDA,
+		}, owners[0]);
+		await program.rpc.approveTransaction({
+			accounts: {
+				multisig: multisigPDA,
+				transaction: rotation.transaction,
+				owner: owners[2].publicKey,
+			},
+			signers: [owners[2]]
+		});
+		await executeTransaction(rotation, owners[2]);
+
+		let multisigInfo = await program.account.multisig.fetch(multisigPDA);
+		assert.equal(multisigInfo.owners.length, 2);
+		assert.equal(multisigInfo.threshold, 1);
+
+		try {
+			await executeTransaction(pending, owners[0]);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The multisig owners changed after the transaction was proposed");
+		}
+	});
+
+});
@@ -0,0 +1,44 @@
+const { assert } = require('chai');
+const {
+	Keypair, connection, program, getTokenAccountBalance, createBuyer, setupPresale,
+	purchaseFractions,
+} = require('./helpers');
+
+describe('fraction-presale opening vesting accounts on purchase', () => {
+
+	var presale;
+	var buyer;
+
+	it('Initialise presale', async () => {
+		presale = await setupPresale({});
+		buyer = await createBuyer(presale, false);
+	});
+
+	it('The first purchase opens the vesting account', async () => {
+		assert.equal(await connection.getAccountInfo(buyer.vestingPDA), null);
+		await purchaseFractions(presale, 100, buyer);
+
+		let vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.signer.toString(), buyer.user.publicKey.toString());
+		assert.equal(vestingInfo.owner.toString(), buyer.user.publicKey.toString());
+		assert.equal(vestingInfo.vestingAccount.toString(), buyer.vestingAccount.publicKey.toString());
+		assert.equal(vestingInfo.totalAmount.toString(), (100 * 1e9).toString());
+	});
+
+	it('Later purchases reuse it', async () => {
+		await purchaseFractions(presale, 200, buyer);
+		let vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.totalAmount.toString(), (300 * 1e9).toString());
+		assert.equal((await getTokenAccountBalance(buyer.vestingAccount.publicKey)).toString(), (300 * 1e9).toString());
+	});
+
+	it('Cannot purchase into another vesting account', async () => {
+		try {
+			await purchaseFractions(presale, 100, { ...buyer, vestingAccount: Keypair.generate(), opened: false });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting account does not belong to this vesting position");
+		}
+	});
+
+});
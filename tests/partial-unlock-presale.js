@@ -0,0 +1,47 @@
+const { assert } = require('chai');
+const {
+	Keypair, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance,
+	setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with partial unlocks', () => {
+
+	var presale;
+	var custodian;
+	var vestingEnd;
+
+	it('Initialise presale and purchase', async () => {
+		vestingEnd = getCurrentTimestamp(15);
+		presale = await setupPresale({ vestingEnd });
+		await purchaseFractions(presale, 1_000);
+		custodian = Keypair.generate();
+	});
+
+	it('Cannot unlock more than has vested', async () => {
+		await sleepUntil(vestingEnd);
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		try {
+			await unlockFractions(presale, toAccount, presale, 1_001);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The amount is more than can currently be unlocked");
+		}
+	});
+
+	it('Unlock part of the position to an account owned by someone else', async () => {
+		let depositAccount = await presale.fractionMint.createAccount(custodian.publicKey);
+		await unlockFractions(presale, depositAccount, presale, 400);
+		assert.equal((await getTokenAccountBalance(depositAccount)).toString(), (400 * 1e9).toString());
+
+		let vestingInfo = await program.account.vestingInfo.fetch(presale.vestingPDA);
+		assert.equal(vestingInfo.claimedAmount.toString(), (400 * 1e9).toString());
+		assert.equal((await getTokenAccountBalance(presale.vestingAccount.publicKey)).toString(), (600 * 1e9).toString());
+	});
+
+	it('Unlock the remainder', async () => {
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (600 * 1e9).toString());
+	});
+
+});
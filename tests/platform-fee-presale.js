@@ -0,0 +1,40 @@
+const { assert } = require('chai');
+const {
+	anchor, SystemProgram, Keypair, connection, program, getCurrentTimestamp, setupPresale,
+} = require('./helpers');
+
+describe('fraction-presale platform fees', () => {
+
+	it('Only the upgrade authority can create the program config', async () => {
+		let [programConfig, configBump] = await anchor.web3.PublicKey.findProgramAddress([Buffer.from("config")], program.programId);
+		let [programData] = await anchor.web3.PublicKey.findProgramAddress(
+			[program.programId.toBuffer()],
+			new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+		);
+		let impostor = Keypair.generate();
+		await connection.confirmTransaction(await connection.requestAirdrop(impostor.publicKey, 1e9));
+
+		try {
+			await program.rpc.initProgramConfig(configBump, impostor.publicKey, 100, {
+				accounts: {
+					programConfig,
+					programData,
+					upgradeAuthority: impostor.publicKey,
+					systemProgram: SystemProgram.programId,
+				},
+				signers: [impostor]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Only the program's upgrade authority can create the program config");
+		}
+	});
+
+	it('Presales created without a config pay no fee', async () => {
+		let presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.feeBps, 0);
+		assert.equal(presaleInfo.feeAccount.toString(), new anchor.web3.PublicKey(0).toString());
+	});
+
+});
@@ -0,0 +1,70 @@
+const { assert } = require('chai');
+const {
+	splToken, SystemProgram, SYSVAR_RENT_PUBKEY, Keypair, program, getCurrentTimestamp, sleepUntil,
+	getTokenAccountBalance, createBuyer, setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with a pooled vesting escrow', () => {
+
+	var presale;
+	var buyer;
+	var vestingEnd;
+
+	it('Initialise presale with a pooled escrow', async () => {
+		vestingEnd = getCurrentTimestamp(20);
+		presale = await setupPresale({ vestingEnd, pooled: true });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.vestingEscrow.toString(), presale.vestingEscrow.toString());
+		buyer = await createBuyer(presale);
+	});
+
+	it('Cannot open a vesting token account per buyer', async () => {
+		let vestingAccount = Keypair.generate();
+		try {
+			await program.rpc.initVestingAccount(buyer.vestingPDABump, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					fractionTreasury: presale.fractionTreasury.publicKey,
+					paymentTreasury: presale.paymentTreasury.publicKey,
+					vestingAccount: vestingAccount.publicKey,
+					userVestingPda: buyer.vestingPDA,
+					fractionMint: presale.fractionMint.publicKey,
+					signer: buyer.user.publicKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+					rent: SYSVAR_RENT_PUBKEY,
+					systemProgram: SystemProgram.programId,
+				},
+				signers: [vestingAccount, buyer.user]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The presale pools vesting fractions in a shared escrow");
+		}
+	});
+
+	it('Purchases are held in the escrow', async () => {
+		await purchaseFractions(presale, 1_000);
+		await purchaseFractions(presale, 500, buyer);
+
+		let vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.vestingAccount.toString(), presale.vestingEscrow.toString());
+		assert.equal(vestingInfo.totalAmount.toString(), (500 * 1e9).toString());
+		assert.equal((await getTokenAccountBalance(presale.vestingEscrow)).toString(), (1_500 * 1e9).toString());
+	});
+
+	it('Unlocks are paid out of the escrow', async () => {
+		await sleepUntil(vestingEnd);
+		let toAccount = await presale.fractionMint.createAccount(buyer.user.publicKey);
+		await unlockFractions(presale, toAccount, buyer);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (500 * 1e9).toString());
+		assert.equal((await getTokenAccountBalance(presale.vestingEscrow)).toString(), (1_000 * 1e9).toString());
+
+		try {
+			await unlockFractions(presale, toAccount, buyer);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "There are no tokens vested in the account");
+		}
+	});
+
+});
@@ -0,0 +1,62 @@
+const { assert } = require('chai');
+const {
+	anchor, program, getCurrentTimestamp, getRegistry, setupPresale,
+} = require('./helpers');
+
+describe('fraction-presale registry', () => {
+
+	const MAX_INDEXED_PRESALES = 32;
+
+	it('Presales are numbered by the registry and listed under their fraction mint', async () => {
+		let registry = await getRegistry();
+		let countBefore = (await program.account.registry.fetch(registry)).presaleCount;
+
+		let presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+		let registryInfo = await program.account.registry.fetch(registry);
+		assert.equal(registryInfo.presaleCount.toString(), countBefore.addn(1).toString());
+
+		let [expectedKey] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("presale"), presale.fractionMint.publicKey.toBuffer(), countBefore.toArrayLike(Buffer, 'le', 8)],
+			program.programId
+		);
+		assert.equal(presale.account.publicKey.toString(), expectedKey.toString());
+
+		let indexInfo = await program.account.presaleIndex.fetch(presale.presaleIndex);
+		assert.equal(indexInfo.fractionMint.toString(), presale.fractionMint.publicKey.toString());
+		assert.equal(indexInfo.page, 0);
+		assert.deepEqual(indexInfo.presales.map(key => key.toString()), [presale.account.publicKey.toString()]);
+	});
+
+	it('A new index page is opened once the previous one is full', async () => {
+		let first = await setupPresale({ vestingEnd: getCurrentTimestamp(20), unstarted: true });
+		const nextPresale = (presaleIndexPage) => setupPresale({
+			vestingEnd: getCurrentTimestamp(20),
+			fractionMint: first.fractionMint,
+			presaleIndexPage,
+			unstarted: true,
+		});
+
+		try {
+			await nextPresale(1);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A new page of the presale index can only be opened once the previous one is full");
+		}
+
+		for (let i = 1; i < MAX_INDEXED_PRESALES; i++) {
+			await nextPresale(0);
+		}
+		try {
+			await nextPresale(0);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "This page of the presale index is full");
+		}
+
+		let presale = await nextPresale(1);
+		let indexInfo = await program.account.presaleIndex.fetch(presale.presaleIndex);
+		assert.equal(indexInfo.page, 1);
+		assert.deepEqual(indexInfo.presales.map(key => key.toString()), [presale.account.publicKey.toString()]);
+	});
+
+});
@@ -0,0 +1,123 @@
+const { assert } = require('chai');
+const {
+	splToken, Keypair, payerKey, program, getCurrentTimestamp, createNativeTokenAccount,
+	setupPresale, purchaseFractions,
+} = require('./helpers');
+
+describe('fraction-presale with separated roles', () => {
+
+	var presale;
+	const operator = Keypair.generate();
+	const treasurer = Keypair.generate();
+	const pauser = Keypair.generate();
+
+	it('Initialise presale and hand out the roles', async () => {
+		presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+		await program.rpc.setRoles(operator.publicKey, treasurer.publicKey, pauser.publicKey, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey,
+			},
+		});
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.operator.toString(), operator.publicKey.toString());
+		assert.equal(presaleInfo.treasurer.toString(), treasurer.publicKey.toString());
+		assert.equal(presaleInfo.pauser.toString(), pauser.publicKey.toString());
+	});
+
+	it('Only the treasurer can collect funds', async () => {
+		let toAccount = await createNativeTokenAccount(0, treasurer);
+		const collectFunds = async (signer) => {
+			await program.rpc.collectFunds(presale.pdaBump, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					paymentTreasury: presale.paymentTreasury.publicKey,
+					toAccount,
+					presalePda: presale.pda,
+					treasurer: signer.publicKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				},
+				signers: [signer]
+			});
+		}
+
+		try {
+			await collectFunds(operator);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+		await collectFunds(treasurer);
+	});
+
+	it('Only the treasurer can set beneficiaries', async () => {
+		let beneficiaries = [{ tokenAccount: await createNativeTokenAccount(0, treasurer), shareBps: 10_000 }];
+		const setBeneficiaries = async (signer) => {
+			await program.rpc.setBeneficiaries(beneficiaries, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					treasurer: signer.publicKey,
+				},
+				signers: [signer]
+			});
+		}
+
+		try {
+			await setBeneficiaries(operator);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+		await setBeneficiaries(treasurer);
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.beneficiaries[0].tokenAccount.toString(), beneficiaries[0].tokenAccount.toString());
+	});
+
+	it('Pauser halts purchases and only the operator resumes them', async () => {
+		const pausePresale = async (signer) => {
+			await program.rpc.pausePresale({
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					pauser: signer.publicKey,
+				},
+				signers: [signer]
+			});
+		}
+		const resumePresale = async (signer) => {
+			await program.rpc.resumePresale({
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					operator: signer.publicKey,
+				},
+				signers: [signer]
+			});
+		}
+
+		try {
+			await pausePresale(operator);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+		await pausePresale(pauser);
+
+		try {
+			await purchaseFractions(presale, 100);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Purchases are paused");
+		}
+
+		try {
+			await resumePresale(pauser);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A has one constraint was violated");
+		}
+		await resumePresale(operator);
+		await purchaseFractions(presale, 100);
+	});
+
+});
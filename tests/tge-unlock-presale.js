@@ -0,0 +1,55 @@
+const { assert } = require('chai');
+const {
+	payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance, setupPresale,
+	purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with a TGE unlock', () => {
+
+	const tgeUnlockBps = 2_500;
+	var presale;
+	var tgeTimestamp;
+	var vestingEnd;
+
+	it('Initialise presale with a TGE unlock', async () => {
+		tgeTimestamp = getCurrentTimestamp(10);
+		vestingEnd = getCurrentTimestamp(20);
+		presale = await setupPresale({ tgeTimestamp, tgeUnlockBps, vestingEnd });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.tgeTimestamp.toString(), tgeTimestamp.toString());
+		assert.equal(presaleInfo.tgeUnlockBps, tgeUnlockBps);
+	});
+
+	it('Cannot unlock before TGE', async () => {
+		await purchaseFractions(presale, 1_000);
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		try {
+			await unlockFractions(presale, toAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting period has not finished");
+		}
+	});
+
+	it('Unlocks the TGE share once, then the remainder after vesting', async () => {
+		await sleepUntil(tgeTimestamp);
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (250 * 1e9).toString());
+
+		try {
+			await unlockFractions(presale, toAccount);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting period has not finished");
+		}
+
+		await sleepUntil(vestingEnd);
+		await unlockFractions(presale, toAccount);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_000 * 1e9).toString());
+
+		let vestingInfo = await program.account.vestingInfo.fetch(presale.vestingPDA);
+		assert.equal(vestingInfo.claimedAmount.toString(), vestingInfo.totalAmount.toString());
+	});
+
+});
@@ -0,0 +1,215 @@
+const { assert } = require('chai');
+const {
+	anchor, splToken, BN, SystemProgram, SYSVAR_RENT_PUBKEY, Keypair, connection, payer, payerKey,
+	program, getCurrentTimestamp, sleep, getTokenAccountBalance, setupPresale,
+} = require('./helpers');
+
+describe('fraction-presale with timelocked admin actions', () => {
+
+	var presale, toAccount;
+	const timelockDelay = 3;
+	const fundsDestination = Keypair.generate().publicKey;
+
+	const queueAction = async (action) => {
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		let [queuedAction, actionBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("action"), presale.account.publicKey.toBuffer(), presaleInfo.queuedActions.toArrayLike(Buffer, 'le', 8)],
+			program.programId
+		);
+		await program.rpc.queueAction(actionBump, action, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				queuedAction,
+				authority: payerKey,
+				payer: payerKey,
+				systemProgram: SystemProgram.programId,
+			},
+		});
+		return queuedAction;
+	}
+
+	const executeAction = async (queuedAction, remainingAccounts=[]) => {
+		await program.rpc.executeAction(presale.pdaBump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				queuedAction,
+				fractionTreasury: presale.fractionTreasury.publicKey,
+				presalePda: presale.pda,
+				authority: payerKey,
+				payer: payerKey,
+				tokenProgram: splToken.TOKEN_PROGRAM_ID,
+			},
+			remainingAccounts,
+		});
+	}
+
+	const setTimelockDelay = async (delay) => {
+		await program.rpc.setTimelockDelay(new BN(delay), {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey,
+			},
+		});
+	}
+
+	it('Initialise presale with a timelock', async () => {
+		presale = await setupPresale({ vestingEnd: getCurrentTimestamp(20) });
+		toAccount = await presale.fractionMint.createAccount(payerKey);
+		await setTimelockDelay(timelockDelay);
+	});
+
+	it('Fractions cannot be removed directly', async () => {
+		try {
+			await program.rpc.removeFractionsForSale(presale.pdaBump, new BN(1e9), {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					fractionTreasury: presale.fractionTreasury.publicKey,
+					paymentTreasury: presale.paymentTreasury.publicKey,
+					toAccount,
+					presalePda: presale.pda,
+					operator: payerKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "This change has to be queued while the presale is timelocked");
+		}
+	});
+
+	it('A multisig cannot take over while timelocked', async () => {
+		let [multisig, multisigBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("multisig"), presale.account.publicKey.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+		try {
+			await program.rpc.createMultisig(multisigBump, [Keypair.generate().publicKey], 1, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					multisig,
+					authority: payerKey,
+					systemProgram: SystemProgram.programId,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "This change has to be queued while the presale is timelocked");
+		}
+	});
+
+	it('Airdrops spend an allowance approved through the queue', async () => {
+		const airdrop = async () => {
+			await program.rpc.airdropFractions(presale.pdaBump, [
+				{ amount: new BN(1e9), vestingPdaBump: presale.vestingPDABump, vestingAccountBump: 0 },
+			], {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					fractionTreasury: presale.fractionTreasury.publicKey,
+					fractionMint: presale.fractionMint.publicKey,
+					presalePda: presale.pda,
+					operator: payerKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+					rent: SYSVAR_RENT_PUBKEY,
+					systemProgram: SystemProgram.programId,
+				},
+				remainingAccounts: [
+					{ pubkey: payerKey, isWritable: false, isSigner: false },
+					{ pubkey: presale.vestingPDA, isWritable: true, isSigner: false },
+					{ pubkey: presale.vestingAccount.publicKey, isWritable: true, isSigner: false },
+				]
+			});
+		}
+
+		try {
+			await airdrop();
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Grants and airdrops must be approved through the timelock queue first");
+		}
+
+		let queuedAction = await queueAction({ approveGrants: { amount: new BN(1e9) } });
+		await sleep(timelockDelay + 1);
+		await executeAction(queuedAction);
+		await airdrop();
+
+		let vestingInfo = await program.account.vestingInfo.fetch(presale.vestingPDA);
+		assert.equal(vestingInfo.totalAmount.toString(), (1e9).toString());
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.grantAllowance.toString(), '0');
+
+		try {
+			await airdrop();
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Grants and airdrops must be approved through the timelock queue first");
+		}
+	});
+
+	it('Queued removals execute once the delay has passed', async () => {
+		let queuedAction = await queueAction({ removeFractions: { amount: new BN(1e9) } });
+		let remainingAccounts = [{ pubkey: toAccount, isSigner: false, isWritable: true }];
+
+		try {
+			await executeAction(queuedAction, remainingAccounts);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The queued action cannot be executed before its timelock expires");
+		}
+
+		await sleep(timelockDelay + 1);
+		await executeAction(queuedAction, remainingAccounts);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1e9).toString());
+		assert.equal(await connection.getAccountInfo(queuedAction), null);
+	});
+
+	it('Queued actions can be cancelled', async () => {
+		let queuedAction = await queueAction({ setFundsDestination: { fundsDestination } });
+		await program.rpc.cancelAction({
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				queuedAction,
+				authority: payerKey,
+				payer: payerKey,
+			},
+		});
+		assert.equal(await connection.getAccountInfo(queuedAction), null);
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.fundsDestination.toString(), new anchor.web3.PublicKey(0).toString());
+	});
+
+	it('Lengthening the delay holds back actions already queued', async () => {
+		let queuedAction = await queueAction({ setFundsDestination: { fundsDestination } });
+		await setTimelockDelay(2 * timelockDelay);
+
+		await sleep(timelockDelay + 1);
+		try {
+			await executeAction(queuedAction);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The queued action cannot be executed before its timelock expires");
+		}
+
+		await sleep(timelockDelay);
+		await executeAction(queuedAction);
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.fundsDestination.toString(), fundsDestination.toString());
+	});
+
+	it('The delay can only be shortened through the queue', async () => {
+		try {
+			await setTimelockDelay(0);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "This change has to be queued while the presale is timelocked");
+		}
+
+		let queuedAction = await queueAction({ setTimelockDelay: { timelockDelay: new BN(0) } });
+		await sleep(2 * timelockDelay + 1);
+		await executeAction(queuedAction);
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.timelockDelay.toString(), "0");
+	});
+
+});
@@ -0,0 +1,65 @@
+const { assert } = require('chai');
+const {
+	anchor, Keypair, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance,
+	setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with unlock delegates', () => {
+
+	var presale;
+	var keeper;
+	var vestingEnd;
+
+	const setUnlockDelegate = async (delegate) => {
+		await program.rpc.setUnlockDelegate(presale.vestingPDABump, delegate, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				userVestingPda: presale.vestingPDA,
+				owner: payerKey,
+			}
+		});
+	}
+
+	it('Initialise presale, purchase and register a delegate', async () => {
+		vestingEnd = getCurrentTimestamp(15);
+		presale = await setupPresale({ vestingEnd });
+		await purchaseFractions(presale, 1_000);
+
+		keeper = Keypair.generate();
+		await setUnlockDelegate(keeper.publicKey);
+		let vestingInfo = await program.account.vestingInfo.fetch(presale.vestingPDA);
+		assert.equal(vestingInfo.delegate.toString(), keeper.publicKey.toString());
+	});
+
+	it('Delegates cannot unlock into their own accounts', async () => {
+		await sleepUntil(vestingEnd);
+		let toAccount = await presale.fractionMint.createAccount(keeper.publicKey);
+		try {
+			await unlockFractions(presale, toAccount, { ...presale, user: keeper });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Delegates can only unlock fractions into the owner's own accounts");
+		}
+	});
+
+	it('Delegates can unlock into the owner\'s account', async () => {
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		await unlockFractions(presale, toAccount, { ...presale, user: keeper }, 400);
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (400 * 1e9).toString());
+	});
+
+	it('Revoked delegates can no longer unlock', async () => {
+		await setUnlockDelegate(null);
+		let vestingInfo = await program.account.vestingInfo.fetch(presale.vestingPDA);
+		assert.equal(vestingInfo.delegate.toString(), new anchor.web3.PublicKey(0).toString());
+
+		let toAccount = await presale.fractionMint.createAccount(payerKey);
+		try {
+			await unlockFractions(presale, toAccount, { ...presale, user: keeper });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The signer does not own this vesting position");
+		}
+	});
+
+});
@@ -0,0 +1,80 @@
+const { assert } = require('chai');
+const {
+	BN, payerKey, program, price, maxAmount, getCurrentTimestamp, setupPresale,
+} = require('./helpers');
+
+describe('fraction-presale updating parameters', () => {
+
+	var presale, presaleEnd, vestingEnd;
+
+	const updatePresaleParams = async ({ price, maxAmount, presaleEnd, vestingEnd }) => {
+		const toBN = (value) => value === undefined ? null : new BN(value);
+		await program.rpc.updatePresaleParams(toBN(price), toBN(maxAmount), toBN(presaleEnd), toBN(vestingEnd), {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				authority: payerKey,
+			},
+		});
+	}
+
+	it('Initialise presale', async () => {
+		presaleEnd = getCurrentTimestamp(100);
+		vestingEnd = getCurrentTimestamp(1000);
+		presale = await setupPresale({ presaleEnd, vestingEnd });
+	});
+
+	it('Price can only move within a bounded share of the starting price', async () => {
+		await updatePresaleParams({ price: price * 1.05 * 1e9 });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.price.toString(), (price * 1.05 * 1e9).toString());
+		assert.equal(presaleInfo.startPrice.toString(), (price * 1e9).toString());
+
+		try {
+			await updatePresaleParams({ price: price * 1.2 * 1e9 });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The price cannot move this far from the price the presale started at");
+		}
+	});
+
+	it('Max amount can only be raised once started', async () => {
+		await updatePresaleParams({ maxAmount: 2 * maxAmount * 1e9 });
+		try {
+			await updatePresaleParams({ maxAmount: maxAmount * 1e9 });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "These presale parameters cannot be changed once the presale has started");
+		}
+	});
+
+	it('Presale end can only be extended, and not past the vesting end', async () => {
+		await updatePresaleParams({ presaleEnd: presaleEnd + 100 });
+		try {
+			await updatePresaleParams({ presaleEnd: presaleEnd });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "These presale parameters cannot be changed once the presale has started");
+		}
+		try {
+			await updatePresaleParams({ presaleEnd: vestingEnd + 1 });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting schedule parameters are invalid");
+		}
+	});
+
+	it('Vesting end can only be brought forward', async () => {
+		await updatePresaleParams({ vestingEnd: vestingEnd - 100 });
+		try {
+			await updatePresaleParams({ vestingEnd: vestingEnd });
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "These presale parameters cannot be changed once the presale has started");
+		}
+
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.presaleEnd.toString(), (presaleEnd + 100).toString());
+		assert.equal(presaleInfo.vestingEnd.toString(), (vestingEnd - 100).toString());
+	});
+
+});
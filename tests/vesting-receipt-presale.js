@@ -0,0 +1,159 @@
+const { assert } = require('chai');
+const {
+	anchor, splToken, BN, SystemProgram, SYSVAR_RENT_PUBKEY, Keypair, connection, payer, payerKey,
+	program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance, derivePosition, createBuyer,
+	setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with vesting receipts', () => {
+
+	var presale;
+	var buyer;
+	var holder;
+	var vestingEnd;
+	var receiptMint;
+	var receiptAccount;
+
+	it('Initialise presale with vesting receipts', async () => {
+		let presaleEnd = getCurrentTimestamp(20);
+		vestingEnd = presaleEnd + 5;
+		presale = await setupPresale({ presaleEnd, vestingEnd, vestingReceipts: true });
+		let presaleInfo = await program.account.presaleInfo.fetch(presale.account.publicKey);
+		assert.equal(presaleInfo.vestingReceipts, true);
+		buyer = await createBuyer(presale);
+	});
+
+	it('Cannot purchase without the receipt accounts', async () => {
+		[receiptMint] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("receipt"), buyer.vestingPDA.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+		try {
+			await purchaseFractions(presale, 1_000, buyer);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting receipt is missing or not held by the signer");
+		}
+	});
+
+	it('The first purchase mints a one-of-one receipt', async () => {
+		let receiptAccountKeypair = Keypair.generate();
+		receiptAccount = receiptAccountKeypair.publicKey;
+		await purchaseFractions(presale, 500, buyer, 0, receiptAccountKeypair);
+
+		let mintInfo = await new splToken.Token(connection, receiptMint, splToken.TOKEN_PROGRAM_ID, payer).getMintInfo();
+		assert.equal(mintInfo.supply.toString(), '1');
+		assert.equal(mintInfo.mintAuthority, null);
+		assert.equal((await getTokenAccountBalance(receiptAccount)).toString(), '1');
+
+		let vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.receiptMint.toString(), receiptMint.toString());
+
+		await purchaseFractions(presale, 500, buyer);
+		vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.totalAmount.toString(), (1_000 * 1e9).toString());
+	});
+
+	it('Airdrops cannot open positions without a receipt', async () => {
+		let recipient = await derivePosition(presale, Keypair.generate());
+		let [recipientVestingAccount, recipientVestingAccountBump] = await anchor.web3.PublicKey.findProgramAddress(
+			[Buffer.from("vesting_account"), recipient.vestingPDA.toBuffer(), program.programId.toBuffer()],
+			program.programId
+		);
+		try {
+			await program.rpc.airdropFractions(presale.pdaBump, [
+				{ amount: new BN(100 * 1e9), vestingPdaBump: recipient.vestingPDABump, vestingAccountBump: recipientVestingAccountBump },
+			], {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					fractionTreasury: presale.fractionTreasury.publicKey,
+					fractionMint: presale.fractionMint.publicKey,
+					presalePda: presale.pda,
+					operator: payerKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+					rent: SYSVAR_RENT_PUBKEY,
+					systemProgram: SystemProgram.programId,
+				},
+				remainingAccounts: [
+					{ pubkey: recipient.user.publicKey, isWritable: false, isSigner: false },
+					{ pubkey: recipient.vestingPDA, isWritable: true, isSigner: false },
+					{ pubkey: recipientVestingAccount, isWritable: true, isSigner: false },
+				]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Presales with vesting receipts can only airdrop into positions that already have one");
+		}
+	});
+
+	it('Tokenized positions cannot register a delegate', async () => {
+		try {
+			await program.rpc.setUnlockDelegate(buyer.vestingPDABump, Keypair.generate().publicKey, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					userVestingPda: buyer.vestingPDA,
+					owner: buyer.user.publicKey,
+				},
+				signers: [buyer.user]
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "Tokenized positions change hands by transferring their receipt");
+		}
+	});
+
+	it('The receipt holder can unlock the position', async () => {
+		holder = Keypair.generate();
+		let receipt = new splToken.Token(connection, receiptMint, splToken.TOKEN_PROGRAM_ID, payer);
+		let holderReceiptAccount = await receipt.createAccount(holder.publicKey);
+		await receipt.transfer(receiptAccount, holderReceiptAccount, buyer.user, [], 1);
+
+		await sleepUntil(vestingEnd);
+
+		let toAccount = await presale.fractionMint.createAccount(buyer.user.publicKey);
+		try {
+			await unlockFractions(presale, toAccount, {
+				...buyer,
+				remainingAccounts: [
+					{ pubkey: receiptMint, isWritable: true, isSigner: false },
+					{ pubkey: receiptAccount, isWritable: true, isSigner: false },
+				],
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The vesting receipt is missing or not held by the signer");
+		}
+
+		toAccount = await presale.fractionMint.createAccount(holder.publicKey);
+		await unlockFractions(presale, toAccount, {
+			...buyer,
+			user: holder,
+			remainingAccounts: [
+				{ pubkey: receiptMint, isWritable: true, isSigner: false },
+				{ pubkey: holderReceiptAccount, isWritable: true, isSigner: false },
+			],
+		});
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_000 * 1e9).toString());
+		assert.equal((await receipt.getMintInfo()).supply.toString(), '0');
+	});
+
+	it('Positions take in no more fractions once their receipt is burned', async () => {
+		try {
+			await program.rpc.grantFractions(presale.pdaBump, buyer.vestingPDABump, new BN(1e9), false, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					fractionTreasury: presale.fractionTreasury.publicKey,
+					vestingAccount: buyer.vestingAccount.publicKey,
+					userVestingPda: buyer.vestingPDA,
+					presalePda: presale.pda,
+					operator: payerKey,
+					tokenProgram: splToken.TOKEN_PROGRAM_ID,
+				},
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "This position was fully claimed and its receipt burned");
+		}
+	});
+
+});
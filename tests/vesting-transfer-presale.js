@@ -0,0 +1,73 @@
+const { assert } = require('chai');
+const {
+	Keypair, payerKey, program, getCurrentTimestamp, sleepUntil, getTokenAccountBalance,
+	createBuyer, setupPresale, purchaseFractions, unlockFractions,
+} = require('./helpers');
+
+describe('fraction-presale with transferable vesting positions', () => {
+
+	var presale;
+	var buyer;
+	var newOwner;
+	var vestingEnd;
+
+	it('Initialise presale and buy a position', async () => {
+		vestingEnd = getCurrentTimestamp(20);
+		presale = await setupPresale({ vestingEnd });
+		buyer = await createBuyer(presale);
+		await purchaseFractions(presale, 1_000, buyer);
+	});
+
+	it('Only the owner can transfer a position', async () => {
+		newOwner = Keypair.generate();
+		try {
+			await program.rpc.transferVestingPosition(buyer.vestingPDABump, {
+				accounts: {
+					presaleAccount: presale.account.publicKey,
+					userVestingPda: buyer.vestingPDA,
+					owner: payerKey,
+					newOwner: newOwner.publicKey,
+					authority: payerKey,
+				}
+			});
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "A raw constraint was violated");
+		}
+	});
+
+	it('Transfer a position to a new owner', async () => {
+		await program.rpc.transferVestingPosition(buyer.vestingPDABump, {
+			accounts: {
+				presaleAccount: presale.account.publicKey,
+				userVestingPda: buyer.vestingPDA,
+				owner: buyer.user.publicKey,
+				newOwner: newOwner.publicKey,
+				authority: payerKey,
+			},
+			signers: [buyer.user]
+		});
+
+		let vestingInfo = await program.account.vestingInfo.fetch(buyer.vestingPDA);
+		assert.equal(vestingInfo.signer.toString(), buyer.user.publicKey.toString());
+		assert.equal(vestingInfo.owner.toString(), newOwner.publicKey.toString());
+		assert.equal(vestingInfo.totalAmount.toString(), (1_000 * 1e9).toString());
+	});
+
+	it('Only the new owner can unlock the position', async () => {
+		await sleepUntil(vestingEnd);
+
+		let toAccount = await presale.fractionMint.createAccount(buyer.user.publicKey);
+		try {
+			await unlockFractions(presale, toAccount, buyer);
+			assert.ok(false);
+		} catch (err) {
+			assert.equal(err.toString(), "The signer does not own this vesting position");
+		}
+
+		toAccount = await presale.fractionMint.createAccount(newOwner.publicKey);
+		await unlockFractions(presale, toAccount, { ...buyer, user: newOwner });
+		assert.equal((await getTokenAccountBalance(toAccount)).toString(), (1_000 * 1e9).toString());
+	});
+
+});